@@ -0,0 +1,40 @@
+//! Times the genetic generator scoring the boards of every generation on one core and on all cores.
+//!
+//! The target can not be reached, so both runs go through the same generations and give the same board.
+//!
+//! ```text
+//! cargo run --release --example parallel_fitness [word-list]
+//! ```
+
+use std::sync::Arc;
+use std::time::Instant;
+use boggle_maker::{BoggleBuilder, BoggleError, CompiledDictionary, GeneticConfig};
+
+fn main() -> Result<(), BoggleError> {
+    let path = std::env::args().nth(1).unwrap_or_else(|| "words.txt".to_string());
+    let dictionary = Arc::new(CompiledDictionary::load(path)?);
+    let cores = std::thread::available_parallelism().map_or(1, |cores| cores.get());
+    println!("{cores} cores");
+
+    for size in [5, 6] {
+        let mut timings = Vec::new();
+        for parallel in [false, true] {
+            let started = Instant::now();
+            let report = BoggleBuilder::new()
+                .with_compiled_dictionary(dictionary.clone())
+                .with_width(size)
+                .with_length(size)
+                .with_target_score(1_000_000)
+                .with_genetic_config(GeneticConfig::fast().with_max_generations(50).with_parallel_fitness(parallel))
+                .with_seed(17)
+                .generate()?
+                .expect("the builder generates a board");
+            let elapsed = started.elapsed();
+            println!("{size}x{size} {}: {elapsed:.2?}, score {}", if parallel { "parallel" } else { "serial" }, report.score());
+            timings.push(elapsed);
+        }
+        println!("{size}x{size} speedup: {:.2}x", timings[0].as_secs_f64() / timings[1].as_secs_f64());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Times loading the dictionary and solving the sample board of the readme,
+//! once for the score and once more for the set of the words found.
+//!
+//! ```text
+//! cargo run --release --example solve [word-list]
+//! ```
+
+use std::time::Instant;
+use boggle_maker::{BoggleBoardSolver, BoggleError};
+
+/// the number of times the board is solved
+const SOLVES: usize = 200;
+
+fn main() -> Result<(), BoggleError> {
+    let path = std::env::args().nth(1).unwrap_or_else(|| "words.txt".to_string());
+    let started = Instant::now();
+    let solver = BoggleBoardSolver::new().with_dictionary(path)?;
+    println!("load: {:.2?}", started.elapsed());
+
+    let board = vec!['S','E','R','S','P','A','T','G','L','I','N','E','S','E','R','S'];
+    let started = Instant::now();
+    let mut score = 0;
+    for _ in 0..SOLVES {
+        score = solver.solve_vec(&board, 4, 4)?.total_score();
+    }
+    println!("{SOLVES} solves: {:.2?}, score {score}", started.elapsed());
+
+    //the set of the words is only made when asked for
+    let started = Instant::now();
+    let mut words = 0;
+    for _ in 0..SOLVES {
+        words = solver.solve_vec(&board, 4, 4)?.words().len();
+    }
+    println!("{SOLVES} solves with the words: {:.2?}, {words} words", started.elapsed());
+
+    Ok(())
+}
@@ -1,31 +1,40 @@
+use std::fmt;
+use rand::Rng;
+use rand::seq::SliceRandom;
 use rand::distr::{Distribution, Uniform};
+use crate::dice::DiceSet;
+use crate::error::BoggleError;
+use crate::tile::{Tile, default_alphabet};
 
-///The board struct
+///The board struct, the cells are stored row by row.
+///
+///the board's width is the number of columns and its length is the number of rows.
+#[derive(Debug)]
 pub struct Board{
     width : usize,
     length : usize,
-    value : Vec<char>,
+    value : Vec<Tile>,
     score : Option<isize>
 }
 
 impl Board{
     
-    ///returns new board instance by given char vector and board width, length and score
-    pub fn new(value : Vec<char>, width : usize ,length : usize, score: Option<isize>) -> Self{
+    ///returns new board instance by given tile (or char) vector and board width, length and score
+    pub fn new<T: Into<Tile>>(value : Vec<T>, width : usize ,length : usize, score: Option<isize>) -> Self{
         Self{
-            width : width,
-            length : length,
-            value : value,
-            score : score,
+            width,
+            length,
+            value : value.into_iter().map(Into::into).collect(),
+            score,
         }
     }
 
-    ///gets board's width
+    ///gets board's width, the number of columns
     pub fn width(&self) -> usize{
         self.width
     }
 
-    ///gets board's length
+    ///gets board's length, the number of rows
     pub fn length(&self) -> usize{
         self.length
     }
@@ -36,30 +45,61 @@ impl Board{
     }
 
     /// gets board's value
-    pub fn value(&self) -> &Vec<char>{
+    pub fn value(&self) -> &[Tile]{
         &self.value
     }
 
     ///generate a random board by given width and length
     pub fn new_random(width : usize, length : usize)->Self{
-        let mut brd = Self{
-            width : width,
-            length : length,
-            value : vec![' '; width * length],
-            score : None
-        };
-
-        let uniform = Uniform::try_from(0..26).unwrap();
-        let mut rng = rand::rng();
-
-        for i in 0..width*length {
-            let rnd = uniform.sample(&mut rng);
-            let random_char = (b'A' + rnd) as char;
-            brd.value[i]= random_char;
-        }
-        
-        
-        brd
+        Self::new_random_from(width, length, &default_alphabet()).expect("the default alphabet is not empty")
+    }
+
+    ///generate a random board by given width and length, picking each cell's tile from the alphabet.
+    ///fails if the alphabet is empty
+    pub fn new_random_from(width : usize, length : usize, alphabet : &[Tile])->Result<Self, BoggleError>{
+        Self::new_random_from_with(width, length, alphabet, &mut rand::rng())
+    }
+
+    ///generate a random board by given width and length using the given random number generator,
+    ///the same seeded generator always gives the same board
+    pub fn new_random_with<R: Rng + ?Sized>(width : usize, length : usize, rng: &mut R)->Self{
+        Self::new_random_from_with(width, length, &default_alphabet(), rng).expect("the default alphabet is not empty")
+    }
+
+    ///generate a random board by given width and length, picking each cell's tile from the alphabet
+    ///using the given random number generator. fails if the alphabet is empty
+    pub fn new_random_from_with<R: Rng + ?Sized>(width : usize, length : usize, alphabet : &[Tile], rng: &mut R)->Result<Self, BoggleError>{
+        let uniform = Uniform::try_from(0..alphabet.len())
+            .map_err(|_| BoggleError::InvalidTile("the alphabet is empty".to_string()))?;
+
+        let value = (0..width*length)
+            .map(|_| alphabet[uniform.sample(rng)])
+            .collect();
+
+        Ok(Self::new(value, width, length, None))
+    }
+
+    ///rolls a board from the given dice set, the dice are shuffled into the board's cells
+    ///and each die shows one of its faces.
+    ///
+    ///blank faces are placed as blocked cells.
+    pub fn roll<R: Rng + ?Sized>(dice: &DiceSet, rng: &mut R) -> Self{
+        let mut order: Vec<usize> = (0..dice.dice().len()).collect();
+        order.shuffle(rng);
+
+        let value = order
+            .into_iter()
+            .map(|index| {
+                let face = dice.dice()[index].roll(rng);
+                if face.is_empty() {
+                    Tile::BLOCKED
+                } else {
+                    Tile::new(face).expect("a die's faces are blank or valid tiles")
+                }
+            })
+            .collect::<Vec<Tile>>();
+
+        Self::new(value, dice.width(), dice.length(), None)
     }
 
     pub fn copy(&self) -> Self{
@@ -71,41 +111,127 @@ impl Board{
         }
     }
 
-    pub fn to_string(&self)->String{
-        let mut s = String::new();
-        for (i,val) in self.value.iter().enumerate() {
-            if i>0 && i%self.width == 0{
-                s.push('\n');
-            }
-            s.push(*val);
-        }
+    ///gets the number of cells in which the two boards have different tiles, cells missing on the smaller board count as different
+    pub fn hamming_distance(&self, other: &Board) -> usize{
+        let different = self.value.iter().zip(&other.value).filter(|(a, b)| a != b).count();
+        different + self.value.len().abs_diff(other.value.len())
+    }
 
-        s
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self)->String{
+        format!("{self}")
     }
 
     pub fn hash(&self)->String{
         let mut s = String::new();
         for val in self.value.iter() {
-            s.push(*val);
+            s.push_str(&val.to_string());
         }
 
         s
     }
 
-    ///gets the char located in (i,j)
-    pub fn get(&self, i:usize,j:usize)->Option<char>{
-        if i>=self.width || j>=self.length
+    ///gets the tile located in row i and column j
+    pub fn get(&self, i:usize,j:usize)->Option<Tile>{
+        if i>=self.length || j>=self.width
         {
             return None;
         }
         Some(self.value[i* self.width + j])
     }  
     
-    ///sets char located in (i,j)
-    pub fn set(&mut self, i:usize,j:usize,ch:char){
-        if !(i>=self.width || j>=self.length)
+    ///sets tile (or char) located in row i and column j
+    pub fn set<T: Into<Tile>>(&mut self, i:usize,j:usize,tile:T){
+        if !(i>=self.length || j>=self.width)
         {
-            self.value[i* self.width + j] = ch;
+            self.value[i* self.width + j] = tile.into();
         }
     }
-}
\ No newline at end of file
+
+    ///marks the cell located in row i and column j as blocked, no word can pass through it
+    pub fn block(&mut self, i:usize,j:usize){
+        self.set(i,j,Tile::BLOCKED);
+    }
+
+    ///whether the cell located in row i and column j is blocked
+    pub fn is_blocked(&self, i:usize,j:usize)->bool{
+        self.get(i,j).is_some_and(|tile| tile.is_blocked())
+    }
+}
+
+impl fmt::Display for Board{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        for (i,val) in self.value.iter().enumerate() {
+            if i>0 && i%self.width == 0{
+                writeln!(f)?;
+            }
+            write!(f, "{val}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::dice::Die;
+
+    #[test]
+    fn rolled_board_uses_every_die_once(){
+        let dice = ["AB","CD","EF","GH","IJ","KL"]
+            .iter()
+            .map(|definition| Die::parse(definition).unwrap())
+            .collect();
+        let dice = DiceSet::new(dice, 3, 2).unwrap();
+
+        let board = Board::roll(&dice, &mut rand::rng());
+        assert_eq!(board.width(), 3);
+        assert_eq!(board.length(), 2);
+
+        let mut dice_used: Vec<usize> = board.value()
+            .iter()
+            .map(|tile| (tile.letters()[0] as u8 - b'A') as usize / 2)
+            .collect();
+        dice_used.sort();
+        assert_eq!(dice_used, vec![0,1,2,3,4,5]);
+    }
+
+    #[test]
+    fn get_and_set_use_rows_and_columns_on_rectangular_boards(){
+        let mut board = Board::new(vec!['A','B','C','D','E','F','G','H','I','J'], 5, 2, None);
+        assert_eq!(board.get(0,4), Some(Tile::from('E')));
+        assert_eq!(board.get(1,0), Some(Tile::from('F')));
+        assert_eq!(board.get(4,0), None);
+        assert_eq!(board.get(2,0), None);
+
+        board.set(1,4,'Z');
+        assert_eq!(board.value()[9], Tile::from('Z'));
+        board.set(4,1,'Y');
+        assert_eq!(board.hash(), "ABCDEFGHIZ");
+        assert_eq!(board.to_string(), "ABCDE\nFGHIZ");
+    }
+
+    #[test]
+    fn rolled_classic_board_only_shows_die_faces(){
+        let dice = DiceSet::classic();
+        let board = Board::roll(&dice, &mut rand::rng());
+
+        for tile in board.value() {
+            assert!(dice.dice().iter().any(|die| die.faces().iter().any(|face| Tile::new(face) == Some(*tile))));
+        }
+    }
+
+    #[test]
+    fn seeded_random_boards_are_reproducible(){
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let board = Board::new_random_with(5, 3, &mut StdRng::seed_from_u64(42));
+        let again = Board::new_random_with(5, 3, &mut StdRng::seed_from_u64(42));
+        let other = Board::new_random_with(5, 3, &mut StdRng::seed_from_u64(43));
+        assert_eq!(board.hash(), again.hash());
+        assert_ne!(board.hash(), other.hash());
+        assert!(matches!(Board::new_random_from(5, 3, &[]), Err(BoggleError::InvalidTile(_))));
+    }
+}
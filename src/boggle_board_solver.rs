@@ -1,31 +1,126 @@
 use std::collections::{HashSet, HashMap};
-use word_trie::trie::Trie;
-use word_trie::TrieBuilder;
-use crate::boggle_dfs::{WordVisitor,BoggleDfsContext,BoggleDfs,get_word_score};
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::io::Read;
+use std::sync::{Arc, OnceLock};
+use crate::boggle_dictionary::{BoggleDictionary, NodeMap};
+use crate::compiled_dictionary::CompiledDictionary;
+use crate::boggle_dfs::{WordNodeVisitor,BoggleDfsContext,BoggleDfs,trace_word,find_words};
 use crate::boggle_board::Board;
+use crate::tile::Tile;
+use crate::scoring::{ScoringRules,ClassicScoring};
+use crate::error::BoggleError;
+use crate::dictionary::load_dictionary;
+
+/// A word found on the board, with every path of cell indexes spelling it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoggleWord {
+    word: String,
+    score: u32,
+    paths: Vec<Vec<u16>>,
+}
+
+impl BoggleWord {
+    fn new(word: String, score: u32) -> Self {
+        Self{
+            word,
+            score,
+            paths: Vec::new(),
+        }
+    }
+
+    /// gets the word
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// gets the word's score
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// gets every distinct path spelling the word, in the order the dfs found them.
+    pub fn paths(&self) -> &[Vec<u16>] {
+        &self.paths
+    }
+
+    /// gets the canonical path of the word, the first one found which is also the
+    /// smallest path when comparing the cell indexes in order.
+    pub fn first_path(&self) -> &[u16] {
+        &self.paths[0]
+    }
+}
+
+/// The reason a player's word is not accepted on a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordRejection {
+    /// the word has fewer letters than the scoring rules' minimum
+    TooShort,
+    /// the word is not in the dictionary
+    NotInDictionary,
+    /// the word can not be traced on the board
+    NotTraceable,
+}
+
+/// A distinct word found on the board, its letters are the `text` bytes from `start` to `end` of the found words.
+#[derive(Debug, Clone, Copy)]
+struct FoundWord {
+    start: usize,
+    end: usize,
+    score: u32,
+}
+
+/// A path found on the board, spelling the found word of the index, its cells are the `cells` from `start` to `end`.
+#[derive(Debug, Clone, Copy)]
+struct FoundPath {
+    word: usize,
+    start: usize,
+    end: usize,
+}
 
 /// Boggle board result after dfs search
 pub struct BoggleBoardResult {
-    words: HashSet<String>,
-    path_tracks: Vec<Vec<u16>>,
+    //the search only appends the found words and paths to these buffers,
+    //the sets and maps of them are only made when asked for
+    text: String,
+    found: Vec<FoundWord>,
+    cells: Vec<u16>,
+    paths: Vec<FoundPath>,
+    words: OnceLock<HashSet<String>>,
+    entries: OnceLock<HashMap<String, BoggleWord>>,
+    path_tracks: OnceLock<Vec<Vec<u16>>>,
+    length_map: OnceLock<HashMap<usize, usize>>,
     counts: Vec<u32>,
-    length_map: HashMap<usize, usize>,
+    score: u32,
+    blocklisted: HashSet<String>,
 }
 
 impl BoggleBoardResult {
     /// Initiate a new instance of BoggleBoardResult.
     pub fn new() -> Self {
         Self{
-            words: HashSet::new(),
-            path_tracks: Vec::new(),
+            text: String::new(),
+            found: Vec::new(),
+            cells: Vec::new(),
+            paths: Vec::new(),
+            words: OnceLock::new(),
+            entries: OnceLock::new(),
+            path_tracks: OnceLock::new(),
+            length_map: OnceLock::new(),
             counts: vec![0;12], 
-            length_map: HashMap::new(),
+            score: 0,
+            blocklisted: HashSet::new(),
         }
     }
 
     /// gets the result for query of how many words exists in the board with specefic score.
     pub fn how_many(&self, score: usize) -> u32 {        
-        if score > 11 { 0 } else { self.counts[score] }        
+        self.counts.get(score).copied().unwrap_or(0)
+    }
+
+    /// gets the board's total score, the sum of all words' scores.
+    pub fn total_score(&self) -> u32 {
+        self.score
     }
 
     /// gets a refrence to all score counts vector
@@ -35,107 +130,231 @@ impl BoggleBoardResult {
 
     /// gets a reference to word length' count map.
     pub fn len_counts(&self) -> &HashMap<usize,usize> {
-        &self.length_map
+        self.length_map.get_or_init(|| {
+            let mut length_map = HashMap::new();
+            for found in &self.found {
+                *length_map.entry(self.found_word(found).chars().count()).or_insert(0) += 1;
+            }
+            length_map
+        })
     }
 
     /// gets the board's all words hash set
     pub fn words(&self) -> &HashSet<String> {
-        &self.words
+        self.words.get_or_init(|| self.found.iter().map(|found| self.found_word(found).to_string()).collect())
+    }
+
+    /// gets the entry of a word found on the board, holding its score and paths.
+    pub fn word_entry(&self, word: &str) -> Option<&BoggleWord> {
+        self.word_entries().get(word)
+    }
+
+    /// gets a reference to all found words' entries map.
+    pub fn word_entries(&self) -> &HashMap<String, BoggleWord> {
+        self.entries.get_or_init(|| {
+            let mut entries: Vec<BoggleWord> = self.found.iter()
+                .map(|found| BoggleWord::new(self.found_word(found).to_string(), found.score))
+                .collect();
+            for path in &self.paths {
+                entries[path.word].paths.push(self.cells[path.start..path.end].to_vec());
+            }
+            entries.into_iter().map(|entry| (entry.word.clone(), entry)).collect()
+        })
     }
 
-    /// gets a vector of all valid paths in the boggle board.
+    /// gets a vector of all valid paths in the boggle board, in the order the dfs found them.
+    ///
+    /// see [`BoggleBoardResult::word_entry`] for the paths of a specific word.
     pub fn path_tracks(&self) -> &Vec<Vec<u16>> {
-        &self.path_tracks
+        self.path_tracks.get_or_init(|| self.paths.iter().map(|path| self.cells[path.start..path.end].to_vec()).collect())
     }
 
-    pub(crate) fn add_word(&mut self, word: String){
+    /// gets every blocklisted word which can be traced on the board, in upper case.
+    ///
+    /// always empty if the solver has no blocklist.
+    pub fn blocklisted_words(&self) -> &HashSet<String> {
+        &self.blocklisted
+    }
+
+    /// whether the word is blocklisted and can be traced on the board
+    pub fn is_blocklisted(&self, word: &str) -> bool {
+        self.blocklisted.contains(&word.to_ascii_uppercase())
+    }
+
+    /// adds a word not found before and gets its index
+    fn add_word(&mut self, word: &str, scoring: &dyn ScoringRules) -> usize {
+        let score = scoring.word_score(word);
         //update the score map
-        let score = get_word_score(&word);
-        self.inc_score(score);       
-        
-        let word_len = word.len();
-        let entry = self.length_map.entry(word_len).or_insert(0);
-        *entry += 1;
+        self.inc_score(score);
 
-        //consume the word memory, this should be last statement
-        self.words.insert(word);
+        let start = self.text.len();
+        self.text.push_str(word);
+        self.found.push(FoundWord { start, end: self.text.len(), score });
+        self.found.len() - 1
     }
 
-    pub(crate) fn add_path(&mut self, path: Vec<u16>){
-        self.path_tracks.push(path);
+    /// adds a path of the found word of the index
+    fn add_path(&mut self, word: usize, path: &[u16]) {
+        let start = self.cells.len();
+        self.cells.extend_from_slice(path);
+        self.paths.push(FoundPath { word, start, end: self.cells.len() });
     }
 
-    fn inc_score(&mut self, score: u32){
-        Self::score_guard(score);
-        self.counts[score as usize] += 1;
+    fn found_word(&self, found: &FoundWord) -> &str {
+        &self.text[found.start..found.end]
     }
 
-    fn score_guard(score: u32){
-        if score > 12 {
-            panic!("the functionality has not been implemented for score bigger than 11");
+    fn inc_score(&mut self, score: u32){
+        let index = score as usize;
+        if index >= self.counts.len() {
+            self.counts.resize(index + 1, 0);
         }
+        self.counts[index] += 1;
+        self.score += score;
     }
 }
 
-struct BoggleBoardSolverVisitor(BoggleBoardResult);
+impl Default for BoggleBoardResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-impl WordVisitor for BoggleBoardSolverVisitor {
-    fn visit(&mut self, word: &str, path: &Vec<u16>){
+//the distinct words are told apart by their dictionary node, mapped to their index in the result
+struct BoggleBoardSolverVisitor<'r, N>(BoggleBoardResult, NodeMap<N, usize>, &'r dyn ScoringRules);
+
+impl<N: Eq + Hash> WordNodeVisitor<N> for BoggleBoardSolverVisitor<'_, N> {
+    fn visit_node(&mut self, node: N, word: &str, path: &[u16]){
+        //add the word to the found words if it is new, and the path to its paths
+        let index = match self.1.entry(node) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => *entry.insert(self.0.add_word(word, self.2)),
+        };
+        self.0.add_path(index, path);
+    }
+}
 
-        //add the path to path_tracks
-        self.0.add_path(path.to_vec());
+/// the boggle board solver struct, solving on a [`CompiledDictionary`] unless built on another [`BoggleDictionary`]
+pub struct BoggleBoardSolver<D: BoggleDictionary = CompiledDictionary> {
+    dictionary: Option<D>,
+    blocklist: Option<CompiledDictionary>,
+    scoring: Arc<dyn ScoringRules>,
+}
 
-        if self.0.words().contains(word) {
-            return;
+impl Default for BoggleBoardSolver {
+    fn default() -> Self {
+        Self {
+            dictionary: None,
+            blocklist: None,
+            scoring: Arc::new(ClassicScoring),
         }
-
-        //add the word to visited list
-        self.0.add_word(word.to_string());
     }
 }
 
-/// the boggle board solver struct
-#[derive(Default)]
-pub struct BoggleBoardSolver (Option<Trie>); 
-
 impl BoggleBoardSolver {
     /// gets new instance of BoggleBoardSolver
     pub fn new() -> Self {
         Self::default()
     }
 
-    ///sets the trie dictionary text file path
-    pub fn with_dictionary<P: Into<String>>(mut self, path: P) -> Result<Self, std::io::Error> {        
-        self.0 = Some(TrieBuilder::new()
-        .from_file(path.into())
-        .expect("Failed to load trie from file"));
+    ///sets the dictionary file path, a word list text file or a compiled dictionary saved by [`CompiledDictionary::save`]
+    pub fn with_dictionary<P: Into<String>>(mut self, path: P) -> Result<Self, BoggleError> {        
+        self.dictionary = Some(load_dictionary(path.into())?);
+        
+        Ok(self)
+    } 
+
+    ///reads the dictionary to its end, a word list text or a compiled dictionary saved by [`CompiledDictionary::save`]
+    pub fn with_dictionary_reader<R: Read>(mut self, reader: R) -> Result<Self, BoggleError> {
+        self.dictionary = Some(CompiledDictionary::read(reader)?);
+
+        Ok(self)
+    }
+
+    ///sets the dictionary's words, e.g. a word list bundled with `include_str!` split in lines
+    pub fn with_words<I>(mut self, words: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.dictionary = Some(CompiledDictionary::from_words(words));
+        self
+    }
+}
+
+impl<D: BoggleDictionary> BoggleBoardSolver<D> {
+    /// gets new instance of BoggleBoardSolver solving on the given dictionary, e.g. an already loaded [`CompiledDictionary`]
+    pub fn from_dictionary(dictionary: D) -> Self {
+        Self {
+            dictionary: Some(dictionary),
+            blocklist: None,
+            scoring: Arc::new(ClassicScoring),
+        }
+    }
+
+    ///sets the text file path of the words to be flagged when found on the board, one word per line
+    pub fn with_blocklist<P: Into<String>>(mut self, path: P) -> Result<Self, BoggleError> {        
+        self.blocklist = Some(load_dictionary(path.into())?);
         
         Ok(self)
     } 
 
-    /// solve a vector representing the boggle board
-    pub fn solve_vec(&self, board: &Vec<char>, width: usize, length: usize) -> Option<BoggleBoardResult> {
-        match &self.0 {
-            Some(trie) => {
-                let context = BoggleDfsContext::new(&trie, width, length);
+    ///sets the scoring rules, defaults to the classic boggle scoring
+    pub fn with_scoring<S: ScoringRules + 'static>(mut self, scoring: S) -> Self {
+        self.scoring = Arc::new(scoring);
+        self
+    }
 
-                if board.len() != context.count() {
-                    panic!("The board size must be fit to the length:{0} and width:{1}", length, width);
-                }
+    /// solve a vector of tiles (or chars) representing the boggle board
+    pub fn solve_vec<T: Copy + Into<Tile>>(&self, board: &[T], width: usize, length: usize) -> Result<BoggleBoardResult, BoggleError> {
+        let dictionary = self.dictionary.as_ref().ok_or(BoggleError::MissingDictionary)?;
+        BoggleError::check_board(width, length, board.len())?;
 
-                let mut visitor = BoggleBoardSolverVisitor(BoggleBoardResult::new());
-                BoggleDfs::new(&context, board).with_visitor(&mut visitor).search();
+        let context = BoggleDfsContext::new(dictionary, width, length);
+        let board: Vec<Tile> = board.iter().map(|tile| (*tile).into()).collect();
+        let mut visitor = BoggleBoardSolverVisitor(BoggleBoardResult::new(), NodeMap::default(), self.scoring.as_ref());
+        BoggleDfs::new(&context, &board).search_nodes(&mut visitor);
 
-                Some(visitor.0)
-            },
-            None => None
+        let mut result = visitor.0;
+        if let Some(blocklist) = &self.blocklist {
+            result.blocklisted = find_words(blocklist, &board, width, length)?;
         }
+
+        Ok(result)
     }
 
     /// solve a boggle board
-    pub fn solve(&self, board: &Board) -> Option<BoggleBoardResult> {
+    pub fn solve(&self, board: &Board) -> Result<BoggleBoardResult, BoggleError> {
         self.solve_vec(board.value(), board.width(), board.length())
     }
+
+    /// checks a word typed by a player against the board.
+    ///
+    /// returns the word with its score and every path spelling it on the board,
+    /// or the reason the word is rejected. fails if no dictionary is loaded or the board is not valid.
+    pub fn check_word(&self, board: &Board, word: &str) -> Result<Result<BoggleWord, WordRejection>, BoggleError> {
+        let dictionary = self.dictionary.as_ref().ok_or(BoggleError::MissingDictionary)?;
+        BoggleError::check_board(board.width(), board.length(), board.value().len())?;
+
+        if word.chars().count() < self.scoring.min_word_length() {
+            return Ok(Err(WordRejection::TooShort));
+        }
+
+        if !dictionary.contains(&word.to_ascii_lowercase()) {
+            return Ok(Err(WordRejection::NotInDictionary));
+        }
+
+        let paths = trace_word(board.value(), board.width(), board.length(), word)?;
+        if paths.is_empty() {
+            return Ok(Err(WordRejection::NotTraceable));
+        }
+
+        let word = word.to_ascii_uppercase();
+        let mut entry = BoggleWord::new(word.clone(), self.scoring.word_score(&word));
+        entry.paths = paths;
+
+        Ok(Ok(entry))
+    }
 }
 
 #[cfg(test)]
@@ -143,7 +362,7 @@ pub mod tests {
     use super::*;
     use crate::builder::BoggleBuilder;
 
-    fn get_sample_solver() -> Result<BoggleBoardSolver, std::io::Error> {
+    fn get_sample_solver() -> Result<BoggleBoardSolver, BoggleError> {
         BoggleBoardSolver::new().with_dictionary("words.txt")
     }
 
@@ -156,7 +375,7 @@ pub mod tests {
         let board = get_sample_board();
 
         let result = solver.solve_vec(&board, 4, 4);
-        assert!(result.is_some(), "the result does not have value");
+        assert!(result.is_ok(), "the result does not have value");
 
         result.unwrap()
     }
@@ -167,6 +386,73 @@ pub mod tests {
         assert!(solver.is_ok(), "Failed to load trie from file");
     }
 
+    #[test]
+    fn bad_input_is_reported_as_an_error(){
+        let solver = get_sample_solver().unwrap();
+        let board = get_sample_board();
+
+        assert!(matches!(solver.solve_vec(&board, 4, 3), Err(BoggleError::InvalidDimensions { width: 4, length: 3, cells: 16 })));
+        assert!(matches!(solver.solve_vec(&board, 0, 16), Err(BoggleError::InvalidDimensions { .. })));
+        assert!(matches!(BoggleBoardSolver::new().solve_vec(&board, 4, 4), Err(BoggleError::MissingDictionary)));
+        assert!(matches!(BoggleBoardSolver::new().with_dictionary("does-not-exist.txt"), Err(BoggleError::Io(_))));
+    }
+
+    #[test]
+    fn a_dictionary_which_is_not_text_fails_to_parse(){
+        let path = std::env::temp_dir().join(format!("boggle-maker-bad-dictionary-{}.txt", std::process::id()));
+        std::fs::write(&path, b"cat\ndog\n\xff\xfe\n").unwrap();
+
+        let result = BoggleBoardSolver::new().with_dictionary(path.to_string_lossy());
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(BoggleError::DictionaryParse { line: 3, .. })));
+    }
+
+    #[test]
+    fn dictionaries_from_readers_and_words_solve_the_same(){
+        let board = get_sample_board();
+        let score = solve_sample_board().total_score();
+        let text = std::fs::read_to_string("words.txt").unwrap();
+
+        let from_text = BoggleBoardSolver::new().with_dictionary_reader(text.as_bytes()).unwrap();
+        assert_eq!(from_text.solve_vec(&board, 4, 4).unwrap().total_score(), score);
+        let compiled = CompiledDictionary::from_words(text.lines()).to_bytes();
+        let from_compiled = BoggleBoardSolver::new().with_dictionary_reader(compiled.as_slice()).unwrap();
+        assert_eq!(from_compiled.solve_vec(&board, 4, 4).unwrap().total_score(), score);
+        assert_eq!(BoggleBoardSolver::from_dictionary(CompiledDictionary::from_words(text.lines())).solve_vec(&board, 4, 4).unwrap().total_score(), score);
+
+        let small = BoggleBoardSolver::new().with_words(["pat", " lines ", "", "zebra"]);
+        let result = small.solve_vec(&board, 4, 4).unwrap();
+        let mut words: Vec<&String> = result.words().iter().collect();
+        words.sort();
+        assert_eq!(words, ["LINES", "PAT"]);
+        assert!(matches!(BoggleBoardSolver::new().with_dictionary_reader(&b"cat\n\xff\n"[..]), Err(BoggleError::DictionaryParse { line: 2, .. })));
+    }
+
+    #[test]
+    fn flags_blocklisted_words_found_on_the_board(){
+        let path = std::env::temp_dir().join(format!("boggle-maker-solver-blocklist-{}.txt", std::process::id()));
+        std::fs::write(&path, "pat\nlines\nzebra\n").unwrap();
+
+        let solver = get_sample_solver().unwrap().with_blocklist(path.to_string_lossy());
+        std::fs::remove_file(&path).unwrap();
+        let result = solver.unwrap().solve_vec(&get_sample_board(), 4, 4).unwrap();
+
+        let mut flagged: Vec<&String> = result.blocklisted_words().iter().collect();
+        flagged.sort();
+        assert_eq!(flagged, ["LINES", "PAT"]);
+        assert!(result.is_blocklisted("pat"));
+        assert!(!result.is_blocklisted("zebra"));
+        assert!(solve_sample_board().blocklisted_words().is_empty());
+
+        //the entries are matched whatever their case
+        std::fs::write(&path, "Pat\nLINES\n").unwrap();
+        let solver = get_sample_solver().unwrap().with_blocklist(path.to_string_lossy());
+        std::fs::remove_file(&path).unwrap();
+        let result = solver.unwrap().solve_vec(&get_sample_board(), 4, 4).unwrap();
+        assert!(result.is_blocklisted("pat") && result.is_blocklisted("lines"));
+        assert_eq!(result.blocklisted_words().len(), 2);
+    }
+
     #[test]
     fn can_solve_sample_board(){
         let _ = solve_sample_board();
@@ -208,6 +494,159 @@ pub mod tests {
         println!("There are {three_count} words with score equal to 3");
     }
 
+    #[test]
+    fn multi_letter_tiles_are_walked_letter_by_letter(){
+        let solver = get_sample_solver().unwrap();
+        let board = vec![
+            Tile::new("Qu").unwrap(), Tile::from('I'), Tile::from('X'),
+            Tile::from('X'), Tile::from('E'), Tile::from('X'),
+            Tile::from('X'), Tile::from('T'), Tile::from('X'),
+        ];
+
+        let result = solver.solve_vec(&board, 3, 3).unwrap();
+        assert!(result.words().contains("QUIET"));
+        assert_eq!(result.len_counts()[&5], 1);
+        assert_eq!(result.how_many(2), 1);
+    }
+
+    #[test]
+    fn scoring_rules_decide_the_score_counts(){
+        let board = get_sample_board();
+        let classic = solve_sample_board();
+        let flat = get_sample_solver().unwrap()
+            .with_scoring(crate::scoring::FlatScoring)
+            .solve_vec(&board, 4, 4)
+            .unwrap();
+
+        let counted = classic.words().iter().filter(|word| word.len() >= 3).count() as u32;
+        assert_eq!(flat.how_many(1), counted);
+        assert_eq!(flat.total_score(), counted);
+        assert_eq!(classic.total_score(), (0..classic.score_counts().len()).map(|score| score as u32 * classic.how_many(score)).sum::<u32>());
+
+        let scrabble = get_sample_solver().unwrap()
+            .with_scoring(crate::scoring::ScrabbleScoring)
+            .solve_vec(&board, 4, 4)
+            .unwrap();
+        let expected: u32 = scrabble.words().iter().map(|word| crate::scoring::ScrabbleScoring.word_score(word)).sum();
+        assert_eq!(scrabble.total_score(), expected);
+        assert_eq!(scrabble.how_many(100), 0);
+    }
+
+    #[test]
+    fn every_word_is_mapped_to_its_paths(){
+        let result = solve_sample_board();
+        assert_eq!(result.word_entries().len(), result.words().len());
+
+        let total_paths: usize = result.word_entries().values().map(|entry| entry.paths().len()).sum();
+        assert_eq!(total_paths, result.path_tracks().len());
+
+        let board = get_sample_board();
+        for entry in result.word_entries().values() {
+            assert!(result.words().contains(entry.word()));
+            assert_eq!(entry.first_path(), entry.paths().iter().min().unwrap().as_slice());
+            for path in entry.paths() {
+                let spelled: String = path.iter().map(|cell| board[*cell as usize]).collect();
+                assert_eq!(spelled, entry.word());
+            }
+        }
+    }
+
+    #[test]
+    fn word_entry_holds_all_distinct_paths(){
+        let solver = get_sample_solver().unwrap();
+        let board = vec!['C','A','X','X','T','C'];
+
+        let result = solver.solve_vec(&board, 3, 2).unwrap();
+        let cat = result.word_entry("CAT").unwrap();
+        assert_eq!(cat.score(), 1);
+        assert_eq!(cat.paths(), [vec![0,1,4], vec![5,1,4]]);
+        assert_eq!(cat.first_path(), [0,1,4]);
+        assert!(result.word_entry("DOG").is_none());
+    }
+
+    #[test]
+    fn check_word_returns_the_path_of_a_valid_word(){
+        let solver = get_sample_solver().unwrap();
+        let board = Board::new(get_sample_board(), 4, 4, None);
+
+        let result = solve_sample_board();
+        for entry in result.word_entries().values().filter(|entry| entry.word().len() >= 3) {
+            let checked = solver.check_word(&board, &entry.word().to_lowercase()).unwrap().unwrap();
+            assert_eq!(&checked, entry);
+        }
+    }
+
+    #[test]
+    fn check_word_traces_multi_letter_tiles(){
+        let solver = get_sample_solver().unwrap();
+        let board = Board::new(vec![
+            Tile::new("Qu").unwrap(), Tile::from('I'), Tile::from('X'),
+            Tile::from('X'), Tile::from('E'), Tile::from('X'),
+            Tile::from('X'), Tile::from('T'), Tile::from('X'),
+        ], 3, 3, None);
+
+        let quiet = solver.check_word(&board, "quiet").unwrap().unwrap();
+        assert_eq!(quiet.word(), "QUIET");
+        assert_eq!(quiet.first_path(), [0,1,4,7]);
+    }
+
+    #[test]
+    fn check_word_explains_rejections(){
+        let solver = get_sample_solver().unwrap();
+        let board = Board::new(get_sample_board(), 4, 4, None);
+
+        assert_eq!(solver.check_word(&board, "at").unwrap(), Err(WordRejection::TooShort));
+        assert_eq!(solver.check_word(&board, "tng").unwrap(), Err(WordRejection::NotInDictionary));
+        assert_eq!(solver.check_word(&board, "zebra").unwrap(), Err(WordRejection::NotTraceable));
+        assert!(matches!(BoggleBoardSolver::new().check_word(&board, "zebra"), Err(BoggleError::MissingDictionary)));
+    }
+
+    #[test]
+    fn blocked_cells_are_impassable(){
+        let solver = get_sample_solver().unwrap();
+        let mut board = Board::new(vec!['C','A','T','X','X','X','X','X','X'], 3, 3, None);
+        assert!(solver.solve(&board).unwrap().words().contains("CAT"));
+
+        board.block(0, 1);
+        assert!(board.is_blocked(0, 1));
+        let result = solver.solve(&board).unwrap();
+        assert!(!result.words().contains("CAT"));
+    }
+
+    #[test]
+    fn can_solve_a_board_wider_than_long(){
+        let solver = get_sample_solver().unwrap();
+        let board = vec![
+            'C','A','T','X','D',
+            'X','X','X','X','O',
+            'X','X','X','X','G',
+        ];
+
+        let result = solver.solve_vec(&board, 5, 3).unwrap();
+        for word in ["CAT", "TAX", "DOG", "GOD"] {
+            assert!(result.words().contains(word), "{word} is not found");
+        }
+        assert!(!result.words().contains("ACT"));
+    }
+
+    #[test]
+    fn can_solve_a_board_longer_than_wide(){
+        let solver = get_sample_solver().unwrap();
+        let board = vec![
+            'C','X','X',
+            'A','X','X',
+            'T','X','D',
+            'X','X','O',
+            'X','X','G',
+        ];
+
+        let result = solver.solve_vec(&board, 3, 5).unwrap();
+        for word in ["CAT", "TAX", "DOG", "GOD"] {
+            assert!(result.words().contains(word), "{word} is not found");
+        }
+        assert!(!result.words().contains("ACT"));
+    }
+
     #[test]
     fn there_should_a_word_with_length_7(){
         let result = solve_sample_board();
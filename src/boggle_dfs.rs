@@ -1,49 +1,64 @@
-use word_trie::trie::{Trie,TrieNode};
+use std::collections::HashSet;
+use crate::boggle_dictionary::BoggleDictionary;
+use crate::compiled_dictionary::CompiledDictionary;
+use crate::tile::Tile;
+use crate::scoring::{ScoringRules,ClassicScoring};
+use crate::error::BoggleError;
 
-/// gets the word score 
+/// gets the word score by the classic boggle rules
+#[deprecated(note = "use `ClassicScoring` or another `ScoringRules` implementation")]
 pub fn get_word_score(word: &str) -> u32{
-    let len = word.len();
-    match len {
-        3 => 1,
-        4 => 1,
-        5 => 2,
-        6 => 3,
-        7 => 5,
-        _ if len >= 8 => 11,
-        _ => 0,
-    }
-}
-
-/// The boggle dfs context struct.
-#[derive(Debug, Clone)]
-pub struct BoggleDfsContext<'a> {
-    dictionary: &'a Trie,
+    ClassicScoring.word_score(word)
+}
+
+/// the number of cells a board can have to be searched with a bitmask of its visited cells
+const MAX_BITMASK_CELLS: usize = u64::BITS as usize;
+
+/// the row and column offsets of a cell's neighbours, in the order of their cell indexes
+const NEIGHBOUR_OFFSETS: [(isize, isize); 8] = [(-1,-1),(-1,0),(-1,1),(0,-1),(0,1),(1,-1),(1,0),(1,1)];
+
+/// The boggle dfs context struct, searching the words of any [`BoggleDictionary`].
+#[derive(Debug)]
+pub struct BoggleDfsContext<'a, D: BoggleDictionary = CompiledDictionary> {
+    dictionary: &'a D,
     length: usize,
     width: usize,
+    //the bitmask of every cell's neighbours, empty for a board of more than 64 cells
+    neighbours: Vec<u64>,
 }
 
-impl <'a> BoggleDfsContext<'a> {
-    /// initiate a new boggle dfs context.
-    pub fn new(dictionary : &'a Trie, width:usize, length:usize)->Self{
+impl <'a, D: BoggleDictionary> BoggleDfsContext<'a, D> {
+    /// initiate a new boggle dfs context for a board of `width` columns and `length` rows.
+    pub fn new(dictionary : &'a D, width:usize, length:usize)->Self{
+        let count = width.saturating_mul(length);
+        let neighbours = if count <= MAX_BITMASK_CELLS {
+            (0..count)
+                .map(|cell| neighbour_cells(cell, width, length).fold(0u64, |cells, next| cells | 1 << next))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         Self{
             dictionary,
             length,
             width,
+            neighbours,
         }
     }
 
-    /// get boggle board's width
+    /// get boggle board's width, the number of columns
     pub fn width(&self) -> usize {
         self.width
     }
 
-    /// get boggle board's length
+    /// get boggle board's length, the number of rows
     pub fn length(&self) -> usize {
         self.length
     }
 
-    /// gets the trie for the boggle board dfs
-    pub fn dictionary(&self) -> &'a Trie {
+    /// gets the dictionary for the boggle board dfs
+    pub fn dictionary(&self) -> &'a D {
         self.dictionary
     }
 
@@ -51,27 +66,81 @@ impl <'a> BoggleDfsContext<'a> {
     pub fn count(&self) -> usize {
         self.width * self.length
     }
+
+    /// gets the bitmask of the cell's neighbours, `None` for a board of more than 64 cells
+    pub(crate) fn neighbour_mask(&self, cell: usize) -> Option<u64> {
+        self.neighbours.get(cell).copied()
+    }
+}
+
+impl<D: BoggleDictionary> Clone for BoggleDfsContext<'_, D> {
+    fn clone(&self) -> Self {
+        Self {
+            dictionary: self.dictionary,
+            length: self.length,
+            width: self.width,
+            neighbours: self.neighbours.clone(),
+        }
+    }
+}
+
+/// gets the indexes of the cell's neighbours on a board of `width` columns and `length` rows, in ascending order
+pub(crate) fn neighbour_cells(cell: usize, width: usize, length: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = ((cell / width) as isize, (cell % width) as isize);
+    NEIGHBOUR_OFFSETS
+        .into_iter()
+        .map(move |(a, b)| (x + a, y + b))
+        .filter(move |&(next_x, next_y)| (0..length as isize).contains(&next_x) && (0..width as isize).contains(&next_y))
+        .map(move |(next_x, next_y)| next_x as usize * width + next_y as usize)
+}
+
+/// the bit of a node's extended letter mask which is always set, for the cells whose first letter is not one of `a` to `z`
+const ANY_LETTER: u8 = 26;
+
+/// the bit of a node's extended letter mask which is never set, for the blocked cells
+const NO_LETTER: u8 = 27;
+
+/// A step of the iterative dfs, a cell on the current path with the neighbours it has not gone on to yet.
+struct DfsFrame<N> {
+    node: N,
+    cell: u16,
+    pending: u64,
+    word_len: usize,
 }
 
 /// The word visitor trait 
 pub trait WordVisitor {
+    #[allow(clippy::ptr_arg)]
     fn visit(&mut self, word: &str, path: &Vec<u16>);    
 }
 
+/// The visitor of the words found by the dfs along with the dictionary node they end on, which tells the distinct words
+/// apart without comparing them.
+pub(crate) trait WordNodeVisitor<N> {
+    fn visit_node(&mut self, node: N, word: &str, path: &[u16]);
+}
+
+impl<N> WordNodeVisitor<N> for () {
+    fn visit_node(&mut self, _node: N, _word: &str, _path: &[u16]) {}
+}
+
 /// The boggle DFS struct
-pub struct BoggleDfs<'a> {    
-    context: &'a BoggleDfsContext<'a>,
+pub struct BoggleDfs<'a, D: BoggleDictionary = CompiledDictionary> {    
+    context: &'a BoggleDfsContext<'a, D>,
     visitors: Vec<&'a mut dyn WordVisitor>,
     visited: Vec<bool>,
     current: String,
-    board: &'a Vec<char>,
+    board: &'a [Tile],
     path: Vec<u16>,
+    //the bit of every cell's first letter in a node's extended letter mask
+    letter_bits: Vec<u8>,
 }
 
-impl<'a> BoggleDfs<'a>{
+impl<'a, D: BoggleDictionary> BoggleDfs<'a, D>{
     ///initiate a new boggle dfs instance
-    pub fn new(context : &'a BoggleDfsContext<'a>,board: &'a Vec<char>) -> Self {
-        let visited = vec![false; context.count()];
+    pub fn new(context : &'a BoggleDfsContext<'a, D>,board: &'a [Tile]) -> Self {
+        //a small board keeps its visited cells in a bitmask
+        let visited = if context.neighbours.is_empty() { vec![false; context.count()] } else { Vec::new() };
         let current = String::new();
         let path = Vec::new();
         Self{
@@ -81,6 +150,7 @@ impl<'a> BoggleDfs<'a>{
             current,
             board,
             path,
+            letter_bits: Vec::new(),
         }
     }
 
@@ -93,69 +163,310 @@ impl<'a> BoggleDfs<'a>{
 
     /// trigger the dfs search
     pub fn search(&mut self){   
-        for i in 0..self.context.width {
-            for j in 0..self.context.length {
-                self.dfs(&self.context.dictionary().root, i, j);
+        self.search_nodes(&mut ());
+    }
+
+    /// triggers the dfs search, also visiting the dictionary node of every word found
+    pub(crate) fn search_nodes(&mut self, nodes: &mut dyn WordNodeVisitor<D::Node<'a>>){
+        if self.context.neighbours.is_empty() {
+            self.search_recursive(nodes);
+        } else {
+            self.search_iterative(nodes);
+        }
+    }
+
+    /// searches a board of any size, recursing once per cell of the current path
+    fn search_recursive(&mut self, nodes: &mut dyn WordNodeVisitor<D::Node<'a>>) {
+        if self.visited.len() < self.context.count() {
+            self.visited = vec![false; self.context.count()];
+        }
+        let root = self.context.dictionary().root();
+        for i in 0..self.context.length {
+            for j in 0..self.context.width {
+                self.dfs(nodes, root, i, j);
+            }
+        }
+    }
+
+    /// searches a board of up to 64 cells with an explicit stack, visiting the words in the same order as the recursive search
+    fn search_iterative(&mut self, nodes: &mut dyn WordNodeVisitor<D::Node<'a>>) {
+        let context = self.context;
+        let root = context.dictionary().root();
+        let mut stack: Vec<DfsFrame<D::Node<'a>>> = Vec::new();
+        let mut visited = 0u64;
+        self.letter_bits = self.board.iter().map(|tile| match tile.letters().first().map(char::to_ascii_lowercase) {
+            Some(letter @ 'a'..='z') => letter as u8 - b'a',
+            Some(_) => ANY_LETTER,
+            None => NO_LETTER,
+        }).collect();
+        for start in 0..context.count() {
+            self.enter(nodes, &mut stack, &mut visited, root, start);
+            while let Some(frame) = stack.last_mut() {
+                if frame.pending == 0 {
+                    //every neighbour is searched, go back to the previous cell
+                    let frame = stack.pop().expect("the frame is on the stack");
+                    visited &= !(1 << frame.cell);
+                    self.current.truncate(frame.word_len);
+                    self.path.pop();
+                    continue;
+                }
+
+                let next = frame.pending.trailing_zeros() as usize;
+                frame.pending &= frame.pending - 1;
+                let node = frame.node;
+                if visited & 1 << next == 0 {
+                    self.enter(nodes, &mut stack, &mut visited, node, next);
+                }
             }
         }
     }
 
-    fn dfs(&mut self,mut node: &TrieNode ,x: usize,y: usize)
+    /// goes on from the node to the cell if the dictionary has its letters, visiting the word it ends if any
+    fn enter(&mut self, nodes: &mut dyn WordNodeVisitor<D::Node<'a>>, stack: &mut Vec<DfsFrame<D::Node<'a>>>, visited: &mut u64, mut node: D::Node<'a>, cell: usize) {
+        let tile = self.cell_value(cell);
+        if tile.is_blocked() {
+            return;
+        }
+        let dictionary = self.context.dictionary();
+        for ch in tile.letters() {
+            match dictionary.step(node, ch.to_ascii_lowercase()) {
+                Some(next_node) => node = next_node,
+                None => return,
+            }
+        }
+
+        *visited |= 1 << cell;
+        let word_len = self.current.len();
+        self.current.extend(tile.letters());
+        self.path.push(cell as u16);
+        if dictionary.is_word(node) {
+            self.visit(nodes, node);
+        }
+
+        //a node without children ends every path through it, and only its letters are stepped to
+        let mut pending = 0;
+        if dictionary.has_children(node) {
+            let letters = u64::from(dictionary.child_letters(node)) | 1 << ANY_LETTER;
+            let mut neighbours = self.context.neighbours[cell] & !*visited;
+            while neighbours != 0 {
+                let next = neighbours.trailing_zeros() as usize;
+                neighbours &= neighbours - 1;
+                pending |= (letters >> self.letter_bits[next] & 1) << next;
+            }
+        }
+        stack.push(DfsFrame { node, cell: cell as u16, pending, word_len });
+    }
+
+    fn dfs(&mut self, nodes: &mut dyn WordNodeVisitor<D::Node<'a>>, mut node: D::Node<'a> ,x: usize,y: usize)
     {        
         let cell_index = x * self.context.width() + y;
-        //if the board's current cell is visited then return.
-        if self.visited[cell_index] {
+        //if the board's current cell is visited or blocked then return.
+        if self.visited[cell_index] || self.cell_value(cell_index).is_blocked() {
             return;
         }
         
         //mark the current board's cell as visited
         self.visited[cell_index] = true;
-        let ch = self.cell_value(cell_index);
+        let tile = self.cell_value(cell_index);
         
-        //check if the trie has this node
-        let ch_index = ch.to_ascii_lowercase();        
-        match node.nodes.get(&ch_index){
-            Some(next_node) => node = next_node,
-            None => {
-                //if the trie current node does not have the board's current cell's letter then revert the status and return 
-                self.visited[cell_index] = false;
-                return;
-            },
-        }
-        //add current cell's letter to the current word
-        self.current.push(ch);
+        //check if the dictionary has a node for every letter of the tile
+        for ch in tile.letters() {
+            let ch_index = ch.to_ascii_lowercase();        
+            match self.context.dictionary().step(node, ch_index){
+                Some(next_node) => node = next_node,
+                None => {
+                    //if the dictionary's current node does not have the board's current cell's letters then revert the status and return 
+                    self.visited[cell_index] = false;
+                    return;
+                },
+            }
+        }
+        //add current cell's letters to the current word
+        self.current.extend(tile.letters());
         self.path.push(cell_index as u16);
 
         //check if the current word is a valid word in the dictionary 
-        if node.is_word { 
-            self.visit();    
+        if self.context.dictionary().is_word(node) { 
+            self.visit(nodes, node);    
         }
 
         //Recursively check all neighbour cells 
-        for (a,b) in [(-1,-1),(-1,0),(-1,1),(0,-1),(0,1),(1,-1),(1,0),(1,1)]{
-            let next_x = x as i8 + a;
-            let next_y = y as i8 + b;
-            if (0..self.context.width() as i8).contains(&next_x) && (0..self.context.length() as i8).contains(&next_y){
-                self.dfs(node, next_x as usize, next_y as usize); 
-            }           
+        for next in neighbour_cells(cell_index, self.context.width(), self.context.length()) {
+            let width = self.context.width();
+            self.dfs(nodes, node, next / width, next % width);
         }
 
-        //Remove current visited cell letter from current word end.
-        self.current.pop();
+        //Remove current visited cell letters from current word end.
+        for _ in tile.letters() {
+            self.current.pop();
+        }
         self.path.pop();
 
         //mark the current cell as not visited 
         self.visited[cell_index] = false;      
     }
 
-    fn visit(&mut self) {
+    fn visit(&mut self, nodes: &mut dyn WordNodeVisitor<D::Node<'a>>, node: D::Node<'a>) {
         // this part needs to be enhanced to use async 
         for visitor in self.visitors.iter_mut() {
             visitor.visit(&self.current, &self.path);    
         } 
+        nodes.visit_node(node, &self.current, &self.path);
     }
 
-    fn cell_value(&self, index: usize) -> char {
+    fn cell_value(&self, index: usize) -> Tile {
         self.board[index]
     }
-}
\ No newline at end of file
+}
+/// gets every distinct word of the dictionary which can be traced on a board of `width` columns and `length` rows,
+/// the words are in upper case. fails if the board does not fit the width and length
+pub fn find_words<D: BoggleDictionary>(dictionary: &D, board: &[Tile], width: usize, length: usize) -> Result<HashSet<String>, BoggleError> {
+    BoggleError::check_board(width, length, board.len())?;
+    let context = BoggleDfsContext::new(dictionary, width, length);
+    let mut collector = WordCollector(HashSet::new());
+    BoggleDfs::new(&context, board).with_visitor(&mut collector).search();
+
+    Ok(collector.0)
+}
+
+struct WordCollector(HashSet<String>);
+
+impl WordVisitor for WordCollector {
+    fn visit(&mut self, word: &str, _path: &Vec<u16>) {
+        if !self.0.contains(word) {
+            self.0.insert(word.to_string());
+        }
+    }
+}
+
+/// gets every path of cell indexes spelling the given word on a board of `width` columns and `length` rows,
+/// the word is matched case insensitively and the paths are in ascending order. fails if the board does not fit the width and length
+pub fn trace_word(board: &[Tile], width: usize, length: usize, word: &str) -> Result<Vec<Vec<u16>>, BoggleError> {
+    Ok(run_tracer(board, width, length, word)?.paths)
+}
+
+/// gets the number of letters of the word's longest prefix which a path of whole tiles spells on a board of `width` columns
+/// and `length` rows, e.g. two letters of `QUIET` for a lone `Qu` tile. fails if the board does not fit the width and length
+pub(crate) fn traced_letters(board: &[Tile], width: usize, length: usize, word: &str) -> Result<usize, BoggleError> {
+    Ok(run_tracer(board, width, length, word)?.longest)
+}
+
+fn run_tracer<'a>(board: &'a [Tile], width: usize, length: usize, word: &str) -> Result<WordTracer<'a>, BoggleError> {
+    BoggleError::check_board(width, length, board.len())?;
+    let word: Vec<char> = word.chars().map(|ch| ch.to_ascii_uppercase()).collect();
+    let mut tracer = WordTracer {
+        board,
+        width,
+        length,
+        word,
+        visited: vec![false; board.len()],
+        path: Vec::new(),
+        paths: Vec::new(),
+        longest: 0,
+    };
+
+    if !tracer.word.is_empty() {
+        for cell in 0..board.len() {
+            tracer.trace(cell, 0);
+        }
+    }
+
+    Ok(tracer)
+}
+
+struct WordTracer<'a> {
+    board: &'a [Tile],
+    width: usize,
+    length: usize,
+    word: Vec<char>,
+    visited: Vec<bool>,
+    path: Vec<u16>,
+    paths: Vec<Vec<u16>>,
+    //the most letters of the word spelled by a path
+    longest: usize,
+}
+
+impl WordTracer<'_> {
+    fn trace(&mut self, cell_index: usize, matched: usize) {
+        let letters = self.board[cell_index].letters();
+        //a visited or blocked cell, or a tile not matching the rest of the word ends the path
+        if self.visited[cell_index] || letters.is_empty() || !self.word[matched..].starts_with(letters) {
+            return;
+        }
+
+        let matched = matched + letters.len();
+        self.longest = self.longest.max(matched);
+        self.path.push(cell_index as u16);
+        if matched == self.word.len() {
+            self.paths.push(self.path.clone());
+        } else {
+            self.visited[cell_index] = true;
+            for next in neighbour_cells(cell_index, self.width, self.length) {
+                self.trace(next, matched);
+            }
+            self.visited[cell_index] = false;
+        }
+        self.path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use crate::dictionary::load_dictionary;
+    use crate::tile::default_alphabet;
+
+    #[derive(Default)]
+    struct PathCollector(Vec<(String, Vec<u16>)>);
+
+    impl WordVisitor for PathCollector {
+        fn visit(&mut self, word: &str, path: &Vec<u16>) {
+            self.0.push((word.to_string(), path.to_vec()));
+        }
+    }
+
+    #[test]
+    fn iterative_search_visits_the_same_paths_as_the_recursive_one(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let mut alphabet = default_alphabet();
+        alphabet.extend([Tile::new("Qu").unwrap(), Tile::BLOCKED]);
+        let mut rng = StdRng::seed_from_u64(21);
+
+        for (width, length) in [(4, 4), (5, 3), (8, 8)] {
+            let board: Vec<Tile> = (0..width * length).map(|_| alphabet[rng.random_range(0..alphabet.len())]).collect();
+            let context = BoggleDfsContext::new(&dictionary, width, length);
+
+            let mut iterative = PathCollector::default();
+            BoggleDfs::new(&context, &board).with_visitor(&mut iterative).search();
+            let mut recursive = PathCollector::default();
+            BoggleDfs::new(&context, &board).with_visitor(&mut recursive).search_recursive(&mut ());
+
+            assert!(!iterative.0.is_empty());
+            assert_eq!(iterative.0, recursive.0);
+        }
+    }
+
+    #[test]
+    fn searches_boards_wider_than_127_cells(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let mut board = vec![Tile::BLOCKED; 200];
+        for (cell, letter) in [(197, 'C'), (198, 'A'), (199, 'T')] {
+            board[cell] = Tile::from(letter);
+        }
+
+        let words = find_words(&dictionary, &board, 200, 1).unwrap();
+        assert!(words.contains("CAT"));
+    }
+
+    #[test]
+    fn a_board_which_does_not_fit_its_dimensions_is_an_error(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let board: Vec<Tile> = "CATS".chars().map(Tile::from).collect();
+
+        assert!(matches!(find_words(&dictionary, &board, 3, 3), Err(BoggleError::InvalidDimensions { width: 3, length: 3, cells: 4 })));
+        assert!(matches!(trace_word(&board, 3, 3, "cat"), Err(BoggleError::InvalidDimensions { .. })));
+        assert_eq!(trace_word(&board, 4, 1, "cat").unwrap(), vec![vec![0, 1, 2]]);
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::ptr;
+use word_trie::trie::{Trie, TrieNode};
+
+/// The dictionary trait the board searches walk, one letter at a time from the empty prefix.
+///
+/// the solver, the score calculators and the generation strategies all work on any dictionary,
+/// the genetic strategy needs it to be `Sync`, as it may score the boards on several threads.
+///
+/// a node stands for a prefix of the dictionary's words, the letters are stepped in lower case.
+/// the same prefix must always give equal nodes, since the distinct words found are told apart by their nodes.
+/// a node is shared with the threads scoring the boards in parallel, so it must be `Send` and `Sync`.
+pub trait BoggleDictionary {
+    /// a prefix of the dictionary's words
+    type Node<'d>: Copy + Eq + Hash + Send + Sync where Self: 'd;
+
+    /// gets the node of the empty prefix
+    fn root(&self) -> Self::Node<'_>;
+
+    /// gets the node of the prefix which is the node's prefix followed by the letter, if any word starts with it
+    fn step<'d>(&'d self, node: Self::Node<'d>, letter: char) -> Option<Self::Node<'d>>;
+
+    /// whether the node's prefix is a word
+    fn is_word(&self, node: Self::Node<'_>) -> bool;
+
+    /// whether any word is longer than the node's prefix, the searches stop early on a node without children.
+    ///
+    /// defaults to `true`, which is always correct but searches on past the last letter of a word.
+    fn has_children(&self, _node: Self::Node<'_>) -> bool {
+        true
+    }
+
+    /// gets the bit mask of the letters `a` to `z` the node has children for, the bit of `a` first.
+    /// the searches only step to the cells whose first letter is in it, or is not one of `a` to `z`.
+    ///
+    /// defaults to every letter, which is always correct but steps to every neighbour.
+    fn child_letters(&self, _node: Self::Node<'_>) -> u32 {
+        (1 << 26) - 1
+    }
+
+    /// whether the word is in the dictionary, the word is walked as it is
+    fn contains(&self, word: &str) -> bool {
+        word.chars()
+            .try_fold(self.root(), |node, letter| self.step(node, letter))
+            .is_some_and(|node| self.is_word(node))
+    }
+}
+
+/// the map of the nodes of a dictionary, hashed by a [`NodeHasher`]
+pub(crate) type NodeMap<N, V> = HashMap<N, V, BuildHasherDefault<NodeHasher>>;
+
+/// the set of the nodes of a dictionary, hashed by a [`NodeHasher`]
+pub(crate) type NodeSet<N> = HashSet<N, BuildHasherDefault<NodeHasher>>;
+
+/// The hasher of the dictionary nodes the searches tell the words apart by.
+///
+/// a node is a small key hashed once per word found, a multiply and a rotation are enough for it
+/// where the default hasher is made to stand crafted keys.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct NodeHasher(u64);
+
+impl NodeHasher {
+    fn add(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+}
+
+impl Hasher for NodeHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.add(u64::from(n));
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.add(n);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.add(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        //the product's high bits are mixed best, and the low bits pick the bucket
+        self.0.rotate_left(26)
+    }
+}
+
+/// A node of a [`Trie`], two nodes are equal if they are the same node of the same trie.
+#[derive(Debug, Clone, Copy)]
+pub struct TrieNodeRef<'d>(&'d TrieNode);
+
+impl<'d> TrieNodeRef<'d> {
+    /// gets the trie node
+    pub fn node(&self) -> &'d TrieNode {
+        self.0
+    }
+}
+
+impl PartialEq for TrieNodeRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for TrieNodeRef<'_> {}
+
+impl Hash for TrieNodeRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ptr::hash(self.0, state)
+    }
+}
+
+impl BoggleDictionary for Trie {
+    type Node<'d> = TrieNodeRef<'d>;
+
+    fn root(&self) -> TrieNodeRef<'_> {
+        TrieNodeRef(&self.root)
+    }
+
+    fn step<'d>(&'d self, node: TrieNodeRef<'d>, letter: char) -> Option<TrieNodeRef<'d>> {
+        node.0.nodes.get(&letter).map(TrieNodeRef)
+    }
+
+    fn is_word(&self, node: TrieNodeRef<'_>) -> bool {
+        node.0.is_word
+    }
+
+    fn has_children(&self, node: TrieNodeRef<'_>) -> bool {
+        !node.0.nodes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boggle_board_solver::BoggleBoardSolver;
+    use crate::boggle_dfs::find_words;
+    use crate::compiled_dictionary::CompiledDictionary;
+    use crate::dictionary::read_trie;
+    use crate::tile::Tile;
+    use crate::total_boggle_score_calculator::TotalBoggleScoreCalculator;
+    use crate::generation::{GenerationContext, GenerationStrategy, TargetScore};
+    use crate::genetic_boggle_maker::GeneticStrategy;
+    use crate::genetic_config::GeneticConfig;
+    use crate::simple_genetic_boggle_maker::SimpleGeneticStrategy;
+    use crate::local_search_boggle_maker::HillClimbingStrategy;
+
+    /// a sorted word list walked by binary search, a node is the prefix as a slice of its first word
+    struct SortedWords(Vec<String>);
+
+    impl BoggleDictionary for SortedWords {
+        type Node<'d> = &'d str;
+
+        fn root(&self) -> &str {
+            ""
+        }
+
+        fn step<'d>(&'d self, node: &'d str, letter: char) -> Option<&'d str> {
+            let prefix = format!("{node}{letter}");
+            let first = &self.0[self.0.partition_point(|word| word.as_str() < prefix.as_str())..];
+            first.first().filter(|word| word.starts_with(&prefix)).map(|word| &word[..prefix.len()])
+        }
+
+        fn is_word(&self, node: &str) -> bool {
+            self.0.binary_search_by(|word| word.as_str().cmp(node)).is_ok()
+        }
+    }
+
+    #[test]
+    fn every_dictionary_finds_the_same_words(){
+        let trie = read_trie(std::io::BufReader::new(std::fs::File::open("words.txt").unwrap())).unwrap();
+        let compiled = CompiledDictionary::compile(&trie);
+        let mut words: Vec<String> = std::fs::read_to_string("words.txt").unwrap()
+            .lines()
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect();
+        words.sort();
+        words.dedup();
+        let sorted = SortedWords(words);
+
+        let mut board: Vec<Tile> = "SERSPATGLINESERS".chars().map(Tile::from).collect();
+        board[5] = Tile::new("Qu").unwrap();
+        let found = find_words(&compiled, &board, 4, 4).unwrap();
+        assert!(!found.is_empty());
+        assert_eq!(find_words(&trie, &board, 4, 4).unwrap(), found);
+        assert_eq!(find_words(&sorted, &board, 4, 4).unwrap(), found);
+
+        let score = TotalBoggleScoreCalculator::new(&compiled, 4, 4).score(&board).unwrap();
+        assert_eq!(TotalBoggleScoreCalculator::new(&trie, 4, 4).score(&board).unwrap(), score);
+        let mut incremental = TotalBoggleScoreCalculator::new(&sorted, 4, 4).incremental(&board).unwrap();
+        assert_eq!(incremental.score(), score);
+        assert_eq!(incremental.set(5, Tile::from('A')), TotalBoggleScoreCalculator::new(&compiled, 4, 4).score(incremental.board()).unwrap());
+
+        let solver = BoggleBoardSolver::from_dictionary(trie);
+        assert_eq!(solver.solve_vec(&board, 4, 4).unwrap().total_score(), score);
+        assert!(sorted.contains("lines") && !sorted.contains("line s"));
+    }
+
+    #[test]
+    fn every_strategy_generates_on_any_dictionary(){
+        let trie = read_trie(std::io::BufReader::new(std::fs::File::open("words.txt").unwrap())).unwrap();
+        let blocklist = read_trie("pat".as_bytes()).unwrap();
+        let context = GenerationContext::new(&trie, 4, 4)
+            .with_target(TargetScore::at_least(50))
+            .with_blocklist(&blocklist)
+            .with_seed(7);
+
+        let strategies: [&dyn GenerationStrategy<Trie>; 3] = [
+            &GeneticStrategy::new(GeneticConfig::fast()),
+            &SimpleGeneticStrategy::new(),
+            &HillClimbingStrategy::new(),
+        ];
+        let compiled = CompiledDictionary::compile(&trie);
+        for strategy in strategies {
+            let report = strategy.generate(&context).unwrap().unwrap();
+            let score = TotalBoggleScoreCalculator::new(&compiled, 4, 4).score(report.board().value()).unwrap();
+            assert_eq!(report.score(), score as isize);
+            assert!(context.blocklisted_words(report.board().value()).unwrap().is_empty());
+        }
+    }
+}
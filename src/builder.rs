@@ -1,14 +1,48 @@
-use crate::genetic_boggle_maker;
+use crate::genetic_boggle_maker::GeneticStrategy;
 use crate::boggle_board::Board;
-use word_trie::TrieBuilder;
+use std::sync::Arc;
+use crate::tile::Tile;
+use crate::scoring::ScoringRules;
+use crate::genetic_config::GeneticConfig;
+use crate::generation::{GenerationContext, GenerationReport, GenerationStrategy, TargetScore, Progress, ProgressCallback, CancellationToken};
+use crate::error::BoggleError;
+use crate::dictionary::load_dictionary;
+use crate::boggle_dfs::find_words;
+use std::collections::HashSet;
+use std::io::Read;
+use std::time::Duration;
+use crate::compiled_dictionary::CompiledDictionary;
+
+/// Where the builder gets its dictionary from.
+#[derive(Debug, Clone)]
+enum DictionarySource {
+    /// a file loaded on every build
+    Path(String),
+    /// a dictionary already in memory, shared by every build
+    Compiled(Arc<CompiledDictionary>),
+}
 
 ///The boggle board builder struct
 #[derive(Default)]
 pub struct BoggleBuilder {
     width : Option<usize>,
     length : Option<usize>,
-    target_score: Option<isize>,
-    dictionary_path: Option<String>,
+    target: Option<TargetScore>,
+    dictionary: Option<DictionarySource>,
+    alphabet: Option<Vec<Tile>>,
+    blocked_cells: usize,
+    scoring: Option<Arc<dyn ScoringRules>>,
+    genetic_config: Option<GeneticConfig>,
+    strategy: Option<Arc<dyn GenerationStrategy>>,
+    seed: Option<u64>,
+    required_words: Vec<String>,
+    blocklist_path: Option<String>,
+    locked_cells: Vec<(usize, usize, Tile)>,
+    min_hamming_distance: Option<usize>,
+    max_shared_word_ratio: Option<f64>,
+    progress: Option<ProgressCallback>,
+    cancellation: Option<CancellationToken>,
+    time_budget: Option<Duration>,
 }
 
 impl BoggleBuilder {
@@ -17,15 +51,138 @@ impl BoggleBuilder {
         Self::default()
     }
 
-    ///sets the trie dictionary text file path
+    ///sets the dictionary file path, a word list text file or a compiled dictionary saved by [`crate::CompiledDictionary::save`]
     pub fn with_dictionary_path<P: Into<String>>(mut self, path: P) -> Self {
-        self.dictionary_path = Some(path.into());
+        self.dictionary = Some(DictionarySource::Path(path.into()));
         self
     } 
 
-    ///sets the desired target score for the board to be generated
+    ///reads the dictionary to its end, a word list text or a compiled dictionary saved by [`crate::CompiledDictionary::save`].
+    ///fails if the dictionary can not be read
+    pub fn with_dictionary_reader<R: Read>(self, reader: R) -> Result<Self, BoggleError> {
+        Ok(self.with_compiled_dictionary(CompiledDictionary::read(reader)?))
+    }
+
+    ///sets the dictionary's words, e.g. a word list bundled with `include_str!` split in lines
+    pub fn with_words<I>(self, words: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.with_compiled_dictionary(CompiledDictionary::from_words(words))
+    }
+
+    ///sets an already built dictionary, an `Arc` of it can be shared by many builders without copying it
+    pub fn with_compiled_dictionary<D: Into<Arc<CompiledDictionary>>>(mut self, dictionary: D) -> Self {
+        self.dictionary = Some(DictionarySource::Compiled(dictionary.into()));
+        self
+    } 
+
+    ///sets the desired target score for the board to be generated, any board scoring at least `score` meets it
     pub fn with_target_score(mut self, score: isize) -> Self {
-        self.target_score = Some(score);
+        self.target = Some(TargetScore::at_least(score));
+        self
+    } 
+
+    ///sets the desired target score window, the generated board should score between `min` and `max` both inclusive
+    pub fn with_target_range(mut self, min: isize, max: isize) -> Self {
+        self.target = Some(TargetScore::between(min, max));
+        self
+    } 
+
+    ///sets the tiles the board is generated from, e.g. `A` to `Z` with `Qu` in place of `Q`.
+    ///defaults to the `A` to `Z` single letter tiles
+    pub fn with_alphabet(mut self, alphabet: Vec<Tile>) -> Self {
+        self.alphabet = Some(alphabet);
+        self
+    } 
+
+    ///sets the number of cells to be blocked on the generated board, no word can pass through a blocked cell
+    pub fn with_blocked_cells(mut self, count: usize) -> Self {
+        self.blocked_cells = count;
+        self
+    } 
+
+    ///sets the scoring rules the target score is measured by, defaults to the classic boggle scoring
+    pub fn with_scoring<S: ScoringRules + 'static>(mut self, scoring: S) -> Self {
+        self.scoring = Some(Arc::new(scoring));
+        self
+    } 
+
+    ///sets the genetic algorithm parameters, e.g. [`GeneticConfig::fast`] for interactive requests
+    ///or [`GeneticConfig::thorough`] for batch jobs. defaults to [`GeneticConfig::default`]
+    pub fn with_genetic_config(mut self, config: GeneticConfig) -> Self {
+        self.genetic_config = Some(config);
+        self
+    } 
+
+    ///sets the engine generating the boards, e.g. [`SimpleGeneticStrategy`](crate::SimpleGeneticStrategy),
+    ///[`SimulatedAnnealingStrategy`](crate::SimulatedAnnealingStrategy) or [`HillClimbingStrategy`](crate::HillClimbingStrategy).
+    ///defaults to a [`GeneticStrategy`] using the genetic config, which is ignored once a strategy is set
+    pub fn with_strategy<S: GenerationStrategy + 'static>(mut self, strategy: S) -> Self {
+        self.strategy = Some(Arc::new(strategy));
+        self
+    } 
+
+    ///sets the seed of the generation, the same seed, settings and dictionary always generate the same board
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    } 
+
+    ///sets the words every generated board must contain, e.g. a spelling list.
+    ///the generator still optimises toward the target score, and generates no board if it can not fit every word
+    pub fn with_required_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.required_words = words.into_iter().map(Into::into).collect();
+        self
+    } 
+
+    ///sets the text file path of the words no generated board may contain, one word per line.
+    ///the generator generates no board if it can not avoid every word
+    pub fn with_blocklist<P: Into<String>>(mut self, path: P) -> Self {
+        self.blocklist_path = Some(path.into());
+        self
+    } 
+
+    ///pins the tile (or char) at the cell in the given row and column, the generator evolves the rest of the board around it.
+    ///e.g. a brand name can be spelled across the top row by locking each of its letters
+    pub fn with_locked_cell<T: Into<Tile>>(mut self, row: usize, column: usize, tile: T) -> Self {
+        self.locked_cells.push((row, column, tile.into()));
+        self
+    } 
+
+    ///sets the minimum number of cells in which every two boards of [`BoggleBuilder::build_many`] differ
+    pub fn with_min_hamming_distance(mut self, distance: usize) -> Self {
+        self.min_hamming_distance = Some(distance);
+        self
+    } 
+
+    ///sets the maximum part of the words, from 0 to 1, which two boards of [`BoggleBuilder::build_many`] may share.
+    ///the ratio is measured against the board with fewer words
+    pub fn with_max_shared_word_ratio(mut self, ratio: f64) -> Self {
+        self.max_shared_word_ratio = Some(ratio);
+        self
+    } 
+
+    ///sets the callback called on every generation with the generation, the best score so far and the elapsed time
+    pub fn with_progress<F: Fn(&Progress) + Send + Sync + 'static>(mut self, callback: F) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    } 
+
+    ///sets the token which cancels the generation from another thread, the best board found so far is returned
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    } 
+
+    ///sets the wall-clock time of each generated board, after which the best board found so far is returned
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
         self
     } 
 
@@ -60,39 +217,170 @@ impl BoggleBuilder {
     ///assert!(false);
     ///}
     /// ```
-    pub fn build(self) -> Result<Option<Board>, std::io::Error> {
-        let width = match self.width {
-            Some(w) => w,
-            None => 4,
-        };
+    ///
+    /// fails if the dictionary can not be loaded, the dimensions are not valid or the alphabet is empty.
+    pub fn build(self) -> Result<Option<Board>, BoggleError> {
+        Ok(self.generate()?.map(GenerationReport::into_board))
+    }
+
+    ///generates a boggle board instance and reports whether its score is in the target window
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use boggle_maker::BoggleBuilder;
+    /// let report = BoggleBuilder::new()
+    ///.with_dictionary_path("words.txt")
+    ///.with_target_range(400, 450)
+    ///.generate()
+    ///.expect("Failed to load trie from words.txt file")
+    ///.expect("the builder did not generate a board");
+    ///
+    ///if report.target_hit() {
+    ///   assert!((400..=450).contains(&report.score()));
+    ///}
+    /// ```
+    ///
+    /// fails if the dictionary or the blocklist can not be loaded, the dimensions are not valid, the alphabet is empty
+    /// or the strategy's config is out of range.
+    pub fn generate(self) -> Result<Option<GenerationReport>, BoggleError> {
+        if let Some(dictionary) = self.dictionary()? {
+            let blocklist = self.blocklist_path.as_ref().map(load_dictionary).transpose()?;
+            let context = self.context(&dictionary, blocklist.as_ref());
+
+            return self.strategy().generate(&context);
+        }
+
+        Ok(None)
+    }
+
+    ///generates `count` distinct boards, loading the dictionary once.
+    ///
+    ///every two boards differ in at least the minimum hamming distance of cells and share at most
+    ///the maximum shared word ratio of their words, if set. fewer boards are returned if the generator
+    ///can not find enough diverse boards in ten attempts per board.
+    ///
+    /// fails if the dictionary or the blocklist can not be loaded, the dimensions are not valid, the alphabet is empty
+    /// or the strategy's config is out of range.
+    pub fn build_many(self, count: usize) -> Result<Vec<Board>, BoggleError> {
+        const ATTEMPTS_PER_BOARD: usize = 10;
 
-        let length = match self.length {
-            Some(l) => l,
-            None => 4,
+        let mut boards: Vec<Board> = Vec::new();
+        let Some(dictionary) = self.dictionary()? else {
+            return Ok(boards);
         };
 
-        let target_score = match self.target_score {
-            Some(score) => score,
-            None => 0,
+        let blocklist = self.blocklist_path.as_ref().map(load_dictionary).transpose()?;
+        let context = self.context(&dictionary, blocklist.as_ref());
+        let strategy = self.strategy();
+        let min_distance = self.min_hamming_distance.unwrap_or(1).max(1);
+        let min_word_length = context.scoring().min_word_length();
+        let words_of = |board: &Board| -> Result<HashSet<String>, BoggleError> {
+            Ok(find_words(dictionary.as_ref(), board.value(), board.width(), board.length())?
+                .into_iter()
+                .filter(|word| word.chars().count() >= min_word_length)
+                .collect())
         };
 
-        if let Some(path) = self.dictionary_path {
-            let trie = TrieBuilder::new()
-            .from_file(path)
-            .expect("Failed to load trie from file");
+        let mut boards_words: Vec<HashSet<String>> = Vec::new();
+        for attempt in 0..count.saturating_mul(ATTEMPTS_PER_BOARD) {
+            if boards.len() == count || self.cancellation.as_ref().is_some_and(CancellationToken::is_cancelled) {
+                break;
+            }
+
+            //a seeded batch is reproducible, every attempt has a seed of its own
+            let attempt_context = match self.seed {
+                Some(seed) => context.clone().with_seed(seed.wrapping_add(attempt as u64)),
+                None => context.clone(),
+            };
+            let Some(report) = strategy.generate(&attempt_context)? else {
+                continue;
+            };
+
+            let board = report.into_board();
+            if boards.iter().any(|other| board.hamming_distance(other) < min_distance) {
+                continue;
+            }
+
+            if let Some(max_ratio) = self.max_shared_word_ratio {
+                let words = words_of(&board)?;
+                if boards_words.iter().any(|other| shared_word_ratio(&words, other) > max_ratio) {
+                    continue;
+                }
+                boards_words.push(words);
+            }
 
-            let board = genetic_boggle_maker::make(width,length,target_score,&trie);
-            return Ok(board);
+            boards.push(board);
         }
 
-        Ok(None)
+        Ok(boards)
+    }
+
+    fn dictionary(&self) -> Result<Option<Arc<CompiledDictionary>>, BoggleError> {
+        match &self.dictionary {
+            Some(DictionarySource::Path(path)) => Ok(Some(Arc::new(load_dictionary(path)?))),
+            Some(DictionarySource::Compiled(dictionary)) => Ok(Some(dictionary.clone())),
+            None => Ok(None),
+        }
+    }
+
+    fn strategy(&self) -> Arc<dyn GenerationStrategy> {
+        match &self.strategy {
+            Some(strategy) => strategy.clone(),
+            None => Arc::new(GeneticStrategy::new(self.genetic_config.clone().unwrap_or_default())),
+        }
+    }
+
+    fn context<'a>(&self, dictionary: &'a CompiledDictionary, blocklist: Option<&'a CompiledDictionary>) -> GenerationContext<'a> {
+        let mut context = GenerationContext::new(dictionary, self.width.unwrap_or(4), self.length.unwrap_or(4))
+            .with_target(self.target.unwrap_or_default())
+            .with_blocked_cells(self.blocked_cells)
+            .with_required_words(self.required_words.clone());
+        for &(row, column, tile) in &self.locked_cells {
+            context = context.with_locked_cell(row, column, tile);
+        }
+        if let Some(alphabet) = &self.alphabet {
+            context = context.with_alphabet(alphabet.clone());
+        }
+        if let Some(scoring) = &self.scoring {
+            context = context.with_scoring(scoring.clone());
+        }
+        if let Some(blocklist) = blocklist {
+            context = context.with_blocklist(blocklist);
+        }
+        if let Some(seed) = self.seed {
+            context = context.with_seed(seed);
+        }
+        if let Some(progress) = &self.progress {
+            context = context.with_progress(progress.clone());
+        }
+        if let Some(token) = &self.cancellation {
+            context = context.with_cancellation(token.clone());
+        }
+        if let Some(budget) = self.time_budget {
+            context = context.with_time_budget(budget);
+        }
+
+        context
     }
 }
 
+/// gets the part of the smaller word set which is also in the other one
+fn shared_word_ratio(words: &HashSet<String>, other: &HashSet<String>) -> f64 {
+    let smaller = words.len().min(other.len());
+    if smaller == 0 {
+        return 0.0;
+    }
+
+    words.intersection(other).count() as f64 / smaller as f64
+}
+
 #[cfg(test)]
 mod tests{
 
     use super::*;
+    use crate::simple_genetic_boggle_maker::SimpleGeneticStrategy;
+    use crate::local_search_boggle_maker::{SimulatedAnnealingStrategy, HillClimbingStrategy};
 
     #[test]
     fn should_generate_a_board_with_3600_score(){
@@ -104,6 +392,341 @@ mod tests{
         should_generate_a_board_with_deired_score(2500);
     }
 
+    #[test]
+    fn should_generate_from_an_in_memory_dictionary(){
+        let dictionary = Arc::new(CompiledDictionary::load("words.txt").unwrap());
+        let boards = BoggleBuilder::new()
+         .with_compiled_dictionary(dictionary.clone())
+         .with_target_score(300)
+         .build_many(2)
+         .unwrap();
+        assert_eq!(boards.len(), 2);
+
+        let board = BoggleBuilder::new()
+         .with_dictionary_reader(dictionary.to_bytes().as_slice())
+         .unwrap()
+         .with_target_score(300)
+         .build()
+         .unwrap()
+         .expect("the builder did not generate a board");
+        assert!(board.score().unwrap() >= 300);
+
+        let board = BoggleBuilder::new()
+         .with_words(["cat", "act", "tack"])
+         .with_target_score(1)
+         .with_required_words(["cat"])
+         .build()
+         .unwrap()
+         .expect("the builder did not generate a board");
+        assert!(board.score().unwrap() >= 1);
+    }
+
+    #[test]
+    fn should_generate_a_board_with_blocked_cells(){
+        let board = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(100)
+         .with_blocked_cells(3)
+         .build()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+
+        let blocked = board.value().iter().filter(|tile| tile.is_blocked()).count();
+        assert_eq!(blocked, 3);
+        assert!(board.score().unwrap()>=100);
+    }
+
+    #[test]
+    fn should_generate_a_board_scored_by_the_given_rules(){
+        use crate::scoring::FlatScoring;
+        use crate::boggle_board_solver::BoggleBoardSolver;
+
+        let board = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(60)
+         .with_scoring(FlatScoring)
+         .build()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+
+        let result = BoggleBoardSolver::new()
+         .with_dictionary("words.txt")
+         .unwrap()
+         .with_scoring(FlatScoring)
+         .solve(&board)
+         .unwrap();
+        assert_eq!(result.total_score() as isize, board.score().unwrap());
+        assert!(board.score().unwrap()>=60);
+    }
+
+    #[test]
+    fn should_generate_a_board_in_the_target_window(){
+        let report = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_range(500, 600)
+         .generate()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+
+        assert!(report.target_hit());
+        assert!((500..=600).contains(&report.score()));
+        assert_eq!(report.board().score(), Some(report.score()));
+    }
+
+    #[test]
+    fn should_stop_at_the_maximum_generations(){
+        //no 4x4 board scores that much, the run only stops by its limits
+        let report = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(1_000_000)
+         .with_genetic_config(GeneticConfig::fast().with_max_generations(3))
+         .generate()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+
+        assert!(!report.target_hit());
+        assert!(report.score() > 0);
+    }
+
+    #[test]
+    fn should_generate_the_same_board_from_the_same_seed(){
+        let generate = |seed| BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(300)
+         .with_blocked_cells(2)
+         .with_genetic_config(GeneticConfig::fast())
+         .with_seed(seed)
+         .build()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+
+        let board = generate(7);
+        let again = generate(7);
+        assert_eq!(board.hash(), again.hash());
+        assert_eq!(board.score(), again.score());
+        assert_ne!(board.hash(), generate(8).hash());
+    }
+
+    #[test]
+    fn should_generate_a_board_containing_the_required_words(){
+        use crate::boggle_dfs::trace_word;
+
+        let board = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(200)
+         .with_required_words(["spell", "word"])
+         .build()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+
+        for word in ["spell", "word"] {
+            assert!(!trace_word(board.value(), 4, 4, word).unwrap().is_empty(), "{word} is not on {board}");
+        }
+        assert!(board.score().unwrap()>=200);
+
+        let mut alphabet = crate::tile::default_alphabet();
+        alphabet.retain(|tile| *tile != Tile::from('Q'));
+        alphabet.push(Tile::new("Qu").unwrap());
+        let board = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(100)
+         .with_alphabet(alphabet)
+         .with_required_words(["quiet"])
+         .build()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+        assert!(!trace_word(board.value(), 4, 4, "quiet").unwrap().is_empty(), "quiet is not on {board}");
+    }
+
+    #[test]
+    fn should_generate_a_board_without_blocklisted_words(){
+        use crate::boggle_dfs::trace_word;
+
+        let blocked = ["ass", "sex", "tit", "rat"];
+        let path = std::env::temp_dir().join(format!("boggle-maker-blocklist-{}.txt", std::process::id()));
+        std::fs::write(&path, blocked.join("\n")).unwrap();
+
+        let board = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(300)
+         .with_blocklist(path.to_string_lossy())
+         .build();
+        std::fs::remove_file(&path).unwrap();
+
+        let board = board
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+        for word in blocked {
+            assert!(trace_word(board.value(), 4, 4, word).unwrap().is_empty(), "{word} is on {board}");
+        }
+    }
+
+    #[test]
+    fn should_generate_a_board_around_the_locked_cells(){
+        let mut builder = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(300)
+         .with_width(5)
+         .with_length(4)
+         .with_blocked_cells(2);
+        for (column, letter) in "RUSTY".chars().enumerate() {
+            builder = builder.with_locked_cell(0, column, letter);
+        }
+
+        let board = builder
+         .build()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+
+        assert!(board.hash().starts_with("RUSTY"));
+        assert_eq!(board.value().iter().filter(|tile| tile.is_blocked()).count(), 2);
+        assert!(board.score().unwrap()>=300);
+    }
+
+    #[test]
+    fn should_generate_many_diverse_boards(){
+        let boards = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(200)
+         .with_genetic_config(GeneticConfig::fast())
+         .with_min_hamming_distance(10)
+         .with_max_shared_word_ratio(0.5)
+         .with_seed(3)
+         .build_many(4)
+         .expect("Failed to load trie from words.txt file");
+
+        assert_eq!(boards.len(), 4);
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let words: Vec<HashSet<String>> = boards
+         .iter()
+         .map(|board| find_words(&dictionary, board.value(), 4, 4).unwrap().into_iter().filter(|word| word.len() >= 3).collect())
+         .collect();
+        for i in 0..boards.len() {
+            assert!(boards[i].score().unwrap()>=200);
+            for j in 0..i {
+                assert!(boards[i].hamming_distance(&boards[j]) >= 10);
+                assert!(shared_word_ratio(&words[i], &words[j]) <= 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn should_return_the_best_board_once_the_time_budget_is_spent(){
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Instant;
+
+        let generations = Arc::new(AtomicUsize::new(0));
+        let counter = generations.clone();
+        let started = Instant::now();
+        //no 4x4 board scores that much, the generation only stops by its budget
+        let report = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(1_000_000)
+         .with_genetic_config(GeneticConfig::thorough())
+         .with_time_budget(Duration::from_millis(300))
+         .with_progress(move |_| { counter.fetch_add(1, Ordering::Relaxed); })
+         .generate()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+
+        assert!(started.elapsed() < Duration::from_secs(30));
+        assert!(generations.load(Ordering::Relaxed) > 0);
+        assert!(!report.target_hit());
+        assert!(report.score() > 0);
+    }
+
+    #[test]
+    fn should_return_the_best_board_once_cancelled(){
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let board = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_score(1_000_000)
+         .with_genetic_config(GeneticConfig::thorough())
+         .with_cancellation(token)
+         .build()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+        assert!(board.score().unwrap() > 0);
+    }
+
+    #[test]
+    fn should_score_in_parallel_without_changing_the_board(){
+        let generate = |parallel| BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_width(5)
+         .with_length(5)
+         .with_target_score(500)
+         .with_genetic_config(GeneticConfig::fast().with_parallel_fitness(parallel))
+         .with_seed(5)
+         .build()
+         .expect("Failed to load trie from words.txt file")
+         .expect("the builder did not generate a board");
+
+        assert_eq!(generate(true).hash(), generate(false).hash());
+    }
+
+    #[test]
+    fn should_fail_on_invalid_input(){
+        let missing = BoggleBuilder::new().with_dictionary_path("does-not-exist.txt").build();
+        assert!(matches!(missing, Err(BoggleError::Io(_))));
+
+        let empty = BoggleBuilder::new().with_dictionary_path("words.txt").with_width(0).build();
+        assert!(matches!(empty, Err(BoggleError::InvalidDimensions { width: 0, .. })));
+
+        let no_population = BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_genetic_config(GeneticConfig::default().with_population_size(0))
+         .build();
+        assert!(matches!(no_population, Err(BoggleError::InvalidConfig(_))));
+
+        let not_a_word = BoggleBuilder::new().with_dictionary_path("words.txt").with_required_words(["it's"]).build();
+        assert!(matches!(not_a_word, Err(BoggleError::InvalidConfig(_))));
+
+        let missing_blocklist = BoggleBuilder::new().with_dictionary_path("words.txt").with_blocklist("does-not-exist.txt").build();
+        assert!(matches!(missing_blocklist, Err(BoggleError::Io(_))));
+
+        let outside = BoggleBuilder::new().with_dictionary_path("words.txt").with_locked_cell(4, 0, 'A').build();
+        assert!(matches!(outside, Err(BoggleError::InvalidConfig(_))));
+
+        let no_tiles = BoggleBuilder::new().with_dictionary_path("words.txt").with_alphabet(Vec::new()).build();
+        assert!(matches!(no_tiles, Err(BoggleError::InvalidTile(_))));
+    }
+
+    #[test]
+    fn generates_with_the_simple_strategy(){
+        let build = || BoggleBuilder::new()
+         .with_dictionary_path("words.txt")
+         .with_target_range(100, 200)
+         .with_locked_cell(0, 0, 'S')
+         .with_strategy(SimpleGeneticStrategy::new().with_max_generations(100))
+         .with_seed(8)
+         .generate()
+         .unwrap()
+         .expect("the simple strategy did not generate a board");
+
+        let report = build();
+        assert!(report.target_hit(), "score {} is out of the window", report.score());
+        assert_eq!(report.board().get(0, 0), Some(Tile::from('S')));
+        assert_eq!(report.board().hash(), build().board().hash());
+    }
+
+    #[test]
+    fn generates_with_the_local_search_strategies(){
+        let build = |builder: BoggleBuilder| builder
+         .with_dictionary_path("words.txt")
+         .with_target_range(200, 300)
+         .with_seed(2)
+         .generate()
+         .unwrap()
+         .expect("the local search did not generate a board");
+
+        assert!(build(BoggleBuilder::new().with_strategy(SimulatedAnnealingStrategy::new())).target_hit());
+        assert!(build(BoggleBuilder::new().with_strategy(HillClimbingStrategy::new())).target_hit());
+    }
+
+    #[allow(clippy::assertions_on_constants)]
     fn should_generate_a_board_with_deired_score(score:isize){
         let builder = BoggleBuilder::new()
          .with_dictionary_path("words.txt")
@@ -0,0 +1,447 @@
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::ops::Range;
+use std::path::Path;
+use crate::boggle_dictionary::BoggleDictionary;
+use crate::dictionary::{list_word, load_dictionary, read_dictionary};
+use crate::error::BoggleError;
+use word_trie::trie::{Trie, TrieNode};
+
+/// the bit of a node's letter mask telling its prefix is a word, the bits below it are the letters `a` to `z`
+const WORD_BIT: u32 = 1 << 31;
+
+/// the bits of a node's letter mask for the letters `a` to `z`
+const LETTER_BITS: u32 = (1 << 26) - 1;
+
+/// the first bytes of a compiled dictionary file, which can not start a UTF-8 word list
+const MAGIC: [u8; 8] = [0x89, b'B', b'D', b'I', b'C', b'\r', b'\n', 0x1a];
+
+/// the size of a compiled dictionary file's header: the magic, the format version, the node count and the checksum
+const HEADER_LEN: usize = MAGIC.len() + 4 + 4 + 8;
+
+/// the size of a node in a compiled dictionary file, its three fields and its letter
+const NODE_LEN: usize = 4 * 4;
+
+/// A node of the compiled dictionary, its children are the `children` nodes from `first_child` on.
+///
+/// the children of the letters `a` to `z` come first, in the order of the letters, and are found by their bits
+/// in the letter mask. the children of any other letter come after them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CompiledNode {
+    first_child: u32,
+    children: u32,
+    letters: u32,
+}
+
+/// The compiled dictionary struct, a trie flattened into arrays for the board searches.
+///
+/// the children of a node are next to each other, and the blocks of children are laid out depth first, so walking down
+/// a word stays in a small part of the arrays. a node is walked to by its index instead of a hash map lookup.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CompiledDictionary {
+    nodes: Vec<CompiledNode>,
+    letters: Vec<char>,
+}
+
+impl CompiledDictionary {
+    /// the index of the root node, the empty prefix
+    pub const ROOT: u32 = 0;
+
+    /// the version of the compiled dictionary file format, a file of another version fails to load
+    pub const FORMAT_VERSION: u32 = 1;
+
+    /// compiles the trie, the same trie always gives the same compiled dictionary
+    pub fn compile(trie: &Trie) -> Self {
+        let mut dictionary = Self {
+            nodes: vec![CompiledNode { first_child: 0, children: 0, letters: 0 }],
+            letters: vec!['\0'],
+        };
+
+        let mut stack: Vec<(usize, &TrieNode)> = vec![(0, &trie.root)];
+        while let Some((index, node)) = stack.pop() {
+            let mut children: Vec<(char, &TrieNode)> = node.nodes.iter().map(|(&letter, child)| (letter, child)).collect();
+            children.sort_unstable_by_key(|&(letter, _)| child_order(letter));
+
+            let first_child = dictionary.nodes.len();
+            let mut letters = if node.is_word { WORD_BIT } else { 0 };
+            for &(letter, _) in &children {
+                letters |= letter_bit(letter);
+                dictionary.nodes.push(CompiledNode { first_child: 0, children: 0, letters: 0 });
+                dictionary.letters.push(letter);
+            }
+            dictionary.nodes[index] = CompiledNode {
+                first_child: first_child as u32,
+                children: children.len() as u32,
+                letters,
+            };
+
+            //the first child's block is laid out next
+            stack.extend(children.iter().enumerate().rev().map(|(position, &(_, child))| (first_child + position, child)));
+        }
+
+        dictionary
+    }
+
+    /// compiles a list of words, the words are trimmed, lower cased and the empty ones are skipped as in a word list file.
+    ///
+    /// the words are sorted in the order of the children, so the nodes are laid out as [`CompiledDictionary::compile`]
+    /// lays out the trie of the same words, without building the trie.
+    pub fn from_words<I>(words: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut words: Vec<String> = words.into_iter().filter_map(|word| list_word(word.as_ref())).collect();
+        words.sort_unstable_by(|a, b| a.chars().map(child_order).cmp(b.chars().map(child_order)));
+        words.dedup();
+
+        let mut dictionary = Self {
+            nodes: vec![CompiledNode { first_child: 0, children: 0, letters: 0 }],
+            letters: vec!['\0'],
+        };
+
+        //every node is laid out with the words starting with its prefix, which is their first `depth` bytes
+        let mut stack: Vec<(usize, usize, Range<usize>)> = vec![(0, 0, 0..words.len())];
+        let mut children: Vec<(usize, Range<usize>)> = Vec::new();
+        while let Some((index, depth, mut prefixed)) = stack.pop() {
+            //the prefix itself is sorted before the longer words
+            let mut letters = 0;
+            if prefixed.start < prefixed.end && words[prefixed.start].len() == depth {
+                letters = WORD_BIT;
+                prefixed.start += 1;
+            }
+
+            let first_child = dictionary.nodes.len();
+            children.clear();
+            while prefixed.start < prefixed.end {
+                let letter = words[prefixed.start][depth..].chars().next().expect("a longer word");
+                let end = prefixed.start + words[prefixed.clone()].partition_point(|word| word[depth..].starts_with(letter));
+                letters |= letter_bit(letter);
+                dictionary.nodes.push(CompiledNode { first_child: 0, children: 0, letters: 0 });
+                dictionary.letters.push(letter);
+                children.push((depth + letter.len_utf8(), prefixed.start..end));
+                prefixed.start = end;
+            }
+            dictionary.nodes[index] = CompiledNode {
+                first_child: first_child as u32,
+                children: children.len() as u32,
+                letters,
+            };
+
+            //the first child's block is laid out next
+            stack.extend(children.drain(..).enumerate().rev().map(|(position, (depth, prefixed))| (first_child + position, depth, prefixed)));
+        }
+
+        dictionary
+    }
+
+    /// gets the node of the prefix which is the node's prefix followed by the letter, if any word starts with it
+    pub fn child(&self, node: u32, letter: char) -> Option<u32> {
+        let CompiledNode { first_child, children, letters } = self.nodes[node as usize];
+        let bit = letter_bit(letter);
+        if bit != 0 {
+            return (letters & bit != 0).then(|| first_child + (letters & (bit - 1)).count_ones());
+        }
+
+        let first = (first_child + (letters & !WORD_BIT).count_ones()) as usize;
+        let last = (first_child + children) as usize;
+        self.letters[first..last]
+            .iter()
+            .position(|&child| child == letter)
+            .map(|position| (first + position) as u32)
+    }
+
+    /// whether the node's prefix is a word
+    pub fn is_word(&self, node: u32) -> bool {
+        self.nodes[node as usize].letters & WORD_BIT != 0
+    }
+
+    /// whether any word is longer than the node's prefix
+    pub fn has_children(&self, node: u32) -> bool {
+        self.nodes[node as usize].children > 0
+    }
+
+    /// gets the node of the prefix, if any word starts with it
+    pub fn prefix(&self, prefix: &str) -> Option<u32> {
+        prefix.chars().try_fold(Self::ROOT, |node, letter| self.child(node, letter))
+    }
+
+    /// whether the word is in the dictionary, the words are matched as they were added
+    pub fn contains(&self, word: &str) -> bool {
+        self.prefix(word).is_some_and(|node| self.is_word(node))
+    }
+
+    /// gets the number of nodes, one more than the number of distinct prefixes of the words
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// gets the number of words
+    pub fn word_count(&self) -> usize {
+        self.nodes.iter().filter(|node| node.letters & WORD_BIT != 0).count()
+    }
+
+    /// loads a dictionary file, either a compiled dictionary saved by [`CompiledDictionary::save`] or a word list,
+    /// one word per line, which is compiled.
+    ///
+    /// fails with [`BoggleError::DictionaryVersion`] if the compiled dictionary was saved in another format version,
+    /// and with [`BoggleError::InvalidDictionary`] if it is damaged.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, BoggleError> {
+        load_dictionary(path)
+    }
+
+    /// reads a dictionary to its end, either a compiled dictionary saved by [`CompiledDictionary::save`] or a word list,
+    /// one word per line, which is compiled. fails as [`CompiledDictionary::load`] does
+    pub fn read<R: Read>(reader: R) -> Result<Self, BoggleError> {
+        read_dictionary(reader)
+    }
+
+    /// saves the compiled dictionary to a file, which loads much faster than the word list it was compiled from
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), BoggleError> {
+        fs::write(path, self.to_bytes())?;
+
+        Ok(())
+    }
+
+    /// gets the compiled dictionary file content: the header, then every node, then every node's letter.
+    /// the numbers are little endian and the checksum covers everything after the header
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut body = Vec::with_capacity(self.nodes.len() * NODE_LEN);
+        for node in &self.nodes {
+            for field in [node.first_child, node.children, node.letters] {
+                body.extend_from_slice(&field.to_le_bytes());
+            }
+        }
+        for &letter in &self.letters {
+            body.extend_from_slice(&u32::from(letter).to_le_bytes());
+        }
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + body.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&Self::FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.nodes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&checksum(&body).to_le_bytes());
+        bytes.extend_from_slice(&body);
+
+        bytes
+    }
+
+    /// reads a compiled dictionary file content, checking its version, its checksum and that every node is valid
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BoggleError> {
+        if !Self::is_compiled(bytes) {
+            return Err(BoggleError::InvalidDictionary("the file is not a compiled dictionary".to_string()));
+        }
+        if bytes.len() < HEADER_LEN {
+            return Err(BoggleError::InvalidDictionary("the header is truncated".to_string()));
+        }
+
+        let version = read_u32(bytes, MAGIC.len());
+        if version != Self::FORMAT_VERSION {
+            return Err(BoggleError::DictionaryVersion { found: version, expected: Self::FORMAT_VERSION });
+        }
+        let count = read_u32(bytes, MAGIC.len() + 4) as usize;
+        let body = &bytes[HEADER_LEN..];
+        if count == 0 || count.checked_mul(NODE_LEN) != Some(body.len()) {
+            return Err(BoggleError::InvalidDictionary(format!("{} bytes do not hold {count} nodes", body.len())));
+        }
+        let expected = u64::from_le_bytes(bytes[HEADER_LEN - 8..HEADER_LEN].try_into().expect("8 bytes"));
+        if checksum(body) != expected {
+            return Err(BoggleError::InvalidDictionary("the checksum does not match, the file is damaged".to_string()));
+        }
+
+        let (node_bytes, letter_bytes) = body.split_at(count * 12);
+        let nodes: Vec<CompiledNode> = node_bytes
+            .chunks_exact(12)
+            .map(|node| CompiledNode {
+                first_child: read_u32(node, 0),
+                children: read_u32(node, 4),
+                letters: read_u32(node, 8),
+            })
+            .collect();
+        let letters = letter_bytes
+            .chunks_exact(4)
+            .map(|letter| char::from_u32(read_u32(letter, 0)))
+            .collect::<Option<Vec<char>>>()
+            .ok_or_else(|| BoggleError::InvalidDictionary("a letter is not a valid character".to_string()))?;
+
+        //the children come after their parent, so every walk down the dictionary ends,
+        //and even a leaf's first child is within the nodes, so no walk steps past them
+        for (index, node) in nodes.iter().enumerate() {
+            let last = node.first_child as usize + node.children as usize;
+            if last > count || node.children > 0 && node.first_child as usize <= index
+                || node.letters & !(WORD_BIT | LETTER_BITS) != 0
+                || (node.letters & LETTER_BITS).count_ones() > node.children {
+                return Err(BoggleError::InvalidDictionary(format!("node {index} is not valid")));
+            }
+        }
+
+        Ok(Self { nodes, letters })
+    }
+
+    /// whether the file content is a compiled dictionary, of any format version
+    pub(crate) fn is_compiled(bytes: &[u8]) -> bool {
+        bytes.starts_with(&MAGIC)
+    }
+}
+
+/// reads the little endian number at the offset
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("4 bytes"))
+}
+
+/// gets the 64 bit FNV-1a hash of the bytes
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// gets the order of the letter among a node's children, the letters `a` to `z` first
+fn child_order(letter: char) -> (bool, char) {
+    (!letter.is_ascii_lowercase(), letter)
+}
+
+/// gets the bit of the letter in a node's letter mask, zero for a letter which is not `a` to `z`
+fn letter_bit(letter: char) -> u32 {
+    if letter.is_ascii_lowercase() { 1 << (letter as u32 - 'a' as u32) } else { 0 }
+}
+
+impl BoggleDictionary for CompiledDictionary {
+    type Node<'d> = u32;
+
+    fn root(&self) -> u32 {
+        Self::ROOT
+    }
+
+    fn step(&self, node: u32, letter: char) -> Option<u32> {
+        self.child(node, letter)
+    }
+
+    fn is_word(&self, node: u32) -> bool {
+        CompiledDictionary::is_word(self, node)
+    }
+
+    fn has_children(&self, node: u32) -> bool {
+        CompiledDictionary::has_children(self, node)
+    }
+
+    fn child_letters(&self, node: u32) -> u32 {
+        self.nodes[node as usize].letters & LETTER_BITS
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        CompiledDictionary::contains(self, word)
+    }
+}
+
+impl Default for CompiledDictionary {
+    /// an empty dictionary, without any word
+    fn default() -> Self {
+        Self::compile(&Trie::default())
+    }
+}
+
+impl From<&Trie> for CompiledDictionary {
+    fn from(trie: &Trie) -> Self {
+        Self::compile(trie)
+    }
+}
+
+impl fmt::Debug for CompiledDictionary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompiledDictionary")
+            .field("nodes", &self.nodes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use word_trie::Dictionary;
+
+    #[test]
+    fn compiled_dictionary_has_the_trie_words_and_prefixes(){
+        let mut trie = Trie::default();
+        for word in ["cat", "cats", "car", "dog", "café", "cañon"] {
+            trie.add_word(word);
+        }
+
+        let dictionary = CompiledDictionary::compile(&trie);
+        assert_eq!(dictionary.word_count(), 6);
+        assert_eq!(dictionary.node_count(), 14);
+        assert!(dictionary.contains("cats") && dictionary.contains("dog"));
+        assert!(dictionary.contains("café") && dictionary.contains("cañon") && !dictionary.contains("caño"));
+        assert!(!dictionary.contains("ca") && !dictionary.contains("cow"));
+
+        let ca = dictionary.prefix("ca").unwrap();
+        assert!(!dictionary.is_word(ca) && dictionary.has_children(ca));
+        assert!(!dictionary.has_children(dictionary.prefix("cats").unwrap()));
+        assert_eq!(dictionary, CompiledDictionary::compile(&trie));
+    }
+
+    #[test]
+    fn words_are_laid_out_as_their_trie(){
+        let words = [" Cats", "cat", "car", "", "dog", "café", "cañon", "caño", "ca", "Zoo", "cat", "a-b", "Émile"];
+        let mut trie = Trie::default();
+        for word in words.iter().filter_map(|word| list_word(word)) {
+            trie.add_word(&word);
+        }
+        assert_eq!(CompiledDictionary::from_words(words), CompiledDictionary::compile(&trie));
+
+        let trie = crate::dictionary::read_trie(std::io::BufReader::new(fs::File::open("words.txt").unwrap())).unwrap();
+        assert_eq!(CompiledDictionary::load("words.txt").unwrap(), CompiledDictionary::compile(&trie));
+    }
+
+    #[test]
+    fn saved_dictionary_loads_back_the_same(){
+        let dictionary = CompiledDictionary::load("words.txt").unwrap();
+        let bytes = dictionary.to_bytes();
+        assert_eq!(CompiledDictionary::from_bytes(&bytes).unwrap(), dictionary);
+
+        let path = std::env::temp_dir().join(format!("boggle-maker-compiled-{}.bin", std::process::id()));
+        dictionary.save(&path).unwrap();
+        let loaded = CompiledDictionary::load(&path);
+        let solved = crate::boggle_board_solver::BoggleBoardSolver::new()
+            .with_dictionary(path.to_string_lossy())
+            .map(|solver| solver.solve_vec(&"SERSPATGLINESERS".chars().collect::<Vec<char>>(), 4, 4).unwrap().total_score());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), dictionary);
+        assert!(solved.unwrap() > 0);
+    }
+
+    #[test]
+    fn damaged_or_outdated_dictionary_fails_to_load(){
+        let mut trie = Trie::default();
+        for word in ["cat", "cats", "dog"] {
+            trie.add_word(word);
+        }
+        let bytes = CompiledDictionary::compile(&trie).to_bytes();
+
+        let mut outdated = bytes.clone();
+        outdated[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&7u32.to_le_bytes());
+        assert!(matches!(CompiledDictionary::from_bytes(&outdated), Err(BoggleError::DictionaryVersion { found: 7, expected: 1 })));
+
+        let mut damaged = bytes.clone();
+        *damaged.last_mut().unwrap() ^= 1;
+        assert!(matches!(CompiledDictionary::from_bytes(&damaged), Err(BoggleError::InvalidDictionary(_))));
+        assert!(matches!(CompiledDictionary::from_bytes(&bytes[..bytes.len() - 4]), Err(BoggleError::InvalidDictionary(_))));
+        assert!(matches!(CompiledDictionary::from_bytes(b"cat\ndog\n"), Err(BoggleError::InvalidDictionary(_))));
+
+        //a node pointing back at its parent is refused even with a matching checksum
+        let mut looping = bytes.clone();
+        looping[HEADER_LEN + 12..HEADER_LEN + 16].copy_from_slice(&0u32.to_le_bytes());
+        let sum = checksum(&looping[HEADER_LEN..]);
+        looping[HEADER_LEN - 8..HEADER_LEN].copy_from_slice(&sum.to_le_bytes());
+        assert!(matches!(CompiledDictionary::from_bytes(&looping), Err(BoggleError::InvalidDictionary(_))));
+
+        let leaf = (0..read_u32(&bytes, MAGIC.len() + 4) as usize)
+            .map(|node| HEADER_LEN + node * 12)
+            .find(|&offset| read_u32(&bytes, offset + 4) == 0)
+            .expect("a dictionary has a leaf");
+        let mut escaping = bytes.clone();
+        escaping[leaf..leaf + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        let sum = checksum(&escaping[HEADER_LEN..]);
+        escaping[HEADER_LEN - 8..HEADER_LEN].copy_from_slice(&sum.to_le_bytes());
+        assert!(matches!(CompiledDictionary::from_bytes(&escaping), Err(BoggleError::InvalidDictionary(_))));
+    }
+}
@@ -0,0 +1,228 @@
+use std::fs;
+use rand::Rng;
+use crate::error::BoggleError;
+use crate::tile::Tile;
+
+const CLASSIC: [&str; 16] = [
+    "AACIOT", "ABILTY", "ABJMOQu", "ACDEMP",
+    "ACELRS", "ADENVZ", "AHMORS", "BIFORX",
+    "DENOSW", "DKNOTU", "EEFHIY", "EGKLUY",
+    "EGINTV", "EHINPS", "ELPSTU", "GILRUW",
+];
+
+const NEW: [&str; 16] = [
+    "AAEEGN", "ABBJOO", "ACHOPS", "AFFKPS",
+    "AOOTTW", "CIMOTU", "DEILRX", "DELRVY",
+    "DISTTY", "EEGHNW", "EEINSU", "EHRTVW",
+    "EIOSST", "ELRTTY", "HIMNUQu", "HLNNRZ",
+];
+
+const BIG: [&str; 25] = [
+    "AAAFRS", "AAEEEE", "AAFIRS", "ADENNN", "AEEEEM",
+    "AEEGMU", "AEGMNN", "AFIRSY", "BJKQuXZ", "CCNSTW",
+    "CEIILT", "CEILPT", "CEIPST", "DDLNOR", "DHHLOR",
+    "DHHNOT", "DHLNOR", "EIIITT", "EMOTTT", "ENSSSU",
+    "FIPRSY", "GORRVW", "HIPRRY", "NOOTUW", "OOOTTU",
+];
+
+const SUPER_BIG: [&str; 36] = [
+    "AAAFRS", "AAEEEE", "AAEEOO", "AAFIRS", "ABDEIO", "ADENNN",
+    "AEEEEM", "AEEGMU", "AEGMNN", "AEILMN", "AEINOU", "AFIRSY",
+    "AnErHeInQuTh", "BBJKXZ", "CCENST", "CDDLNN", "CEIITT", "CEIPST",
+    "CFGNUY", "DDHNOT", "DHHLOR", "DHHNOW", "DHLNOR", "EHILRS",
+    "EIILST", "EILPST", "EIO###", "EMTTTO", "ENSSSU", "GORRVW",
+    "HIRSTV", "HOPRST", "IPRSYY", "JKQuWXZ", "NOOTUW", "OOOTTU",
+];
+
+/// A single boggle die, holding the text printed on each of its faces.
+///
+/// A face is one or more letters (such as `"Qu"`), or an empty string for a blank face.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Die {
+    faces: Vec<String>,
+}
+
+impl Die {
+    /// initiate a new die by given faces.
+    ///
+    /// fails if the die has no face or a face which is neither blank nor a valid tile, e.g. a digit
+    /// or more letters than a tile can hold.
+    pub fn new(faces: Vec<String>) -> Result<Self, BoggleError> {
+        if faces.is_empty() {
+            return Err(BoggleError::InvalidTile("a die must have at least one face".to_string()));
+        }
+
+        if let Some(face) = faces.iter().find(|face| !face.is_empty() && Tile::new(face).is_none()) {
+            return Err(BoggleError::InvalidTile(format!("the die face '{face}' is not blank or 1 to {} letters", Tile::MAX_LEN)));
+        }
+
+        Ok(Self { faces })
+    }
+
+    /// parses a die definition such as `AAEEGN` or `AnErHeInQuTh`.
+    ///
+    /// Every upper case letter starts a new face, lower case letters are appended to the
+    /// face before them and `#` stands for a blank face. Faces can also be separated by spaces.
+    pub fn parse(definition: &str) -> Result<Self, BoggleError> {
+        let mut faces: Vec<String> = Vec::new();
+        // whether the last face can still take lower case letters
+        let mut open = false;
+        for ch in definition.chars() {
+            if ch.is_whitespace() {
+                open = false;
+            } else if ch == '#' {
+                faces.push(String::new());
+                open = false;
+            } else if ch.is_uppercase() {
+                faces.push(ch.to_string());
+                open = true;
+            } else if ch.is_lowercase() && open {
+                if let Some(face) = faces.last_mut() {
+                    face.push(ch);
+                }
+            } else {
+                return Err(BoggleError::InvalidTile(format!("invalid die face character '{ch}' in '{definition}'")));
+            }
+        }
+
+        Self::new(faces)
+    }
+
+    /// gets the die's faces
+    pub fn faces(&self) -> &[String] {
+        &self.faces
+    }
+
+    /// rolls the die and returns the face on top
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> &str {
+        &self.faces[rng.random_range(0..self.faces.len())]
+    }
+}
+
+/// A set of dice, one die per board cell, which boards can be rolled from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceSet {
+    width: usize,
+    length: usize,
+    dice: Vec<Die>,
+}
+
+impl DiceSet {
+    /// initiate a new dice set for a board of the given width and length.
+    ///
+    /// fails if the number of dice does not match the number of board cells.
+    pub fn new(dice: Vec<Die>, width: usize, length: usize) -> Result<Self, BoggleError> {
+        BoggleError::check_board(width, length, dice.len())?;
+
+        Ok(Self { width, length, dice })
+    }
+
+    /// the classic 4x4 boggle dice
+    pub fn classic() -> Self {
+        Self::from_definitions(&CLASSIC, 4, 4)
+    }
+
+    /// the 4x4 dice shipped with newer boggle sets
+    pub fn new_boggle() -> Self {
+        Self::from_definitions(&NEW, 4, 4)
+    }
+
+    /// the 5x5 big boggle dice
+    pub fn big_boggle() -> Self {
+        Self::from_definitions(&BIG, 5, 5)
+    }
+
+    /// the 6x6 super big boggle dice, including the multi-letter and blank faces
+    pub fn super_big_boggle() -> Self {
+        Self::from_definitions(&SUPER_BIG, 6, 6)
+    }
+
+    /// parses a dice set from text, one die per line.
+    ///
+    /// Empty lines and lines starting with `//` are ignored. See [`Die::parse`] for the die format.
+    pub fn parse(text: &str, width: usize, length: usize) -> Result<Self, BoggleError> {
+        let dice = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("//"))
+            .map(Die::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::new(dice, width, length)
+    }
+
+    /// loads a dice set from a text file, one die per line.
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P, width: usize, length: usize) -> Result<Self, BoggleError> {
+        Self::parse(&fs::read_to_string(path)?, width, length)
+    }
+
+    /// gets the board's width this set is made for
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// gets the board's length this set is made for
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// gets the dice of this set
+    pub fn dice(&self) -> &[Die] {
+        &self.dice
+    }
+
+    fn from_definitions(definitions: &[&str], width: usize, length: usize) -> Self {
+        let dice = definitions
+            .iter()
+            .map(|definition| Die::parse(definition).expect("built-in dice are valid"))
+            .collect();
+
+        Self { width, length, dice }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_sets_fit_their_boards(){
+        for set in [DiceSet::classic(), DiceSet::new_boggle(), DiceSet::big_boggle(), DiceSet::super_big_boggle()] {
+            assert_eq!(set.dice().len(), set.width() * set.length());
+            assert!(set.dice().iter().all(|die| die.faces().len() == 6));
+        }
+    }
+
+    #[test]
+    fn parses_multi_letter_and_blank_faces(){
+        let die = Die::parse("AnErHeInQuTh").unwrap();
+        assert_eq!(die.faces(), ["An", "Er", "He", "In", "Qu", "Th"]);
+
+        let die = Die::parse("EIO###").unwrap();
+        assert_eq!(die.faces(), ["E", "I", "O", "", "", ""]);
+
+        let die = Die::parse("A B Qu").unwrap();
+        assert_eq!(die.faces(), ["A", "B", "Qu"]);
+    }
+
+    #[test]
+    fn rejects_invalid_definitions(){
+        assert!(matches!(Die::parse("AB1"), Err(BoggleError::InvalidTile(_))));
+        assert!(matches!(Die::parse(""), Err(BoggleError::InvalidTile(_))));
+        assert!(matches!(Die::parse("AQuuuB"), Err(BoggleError::InvalidTile(_))));
+        assert!(matches!(Die::new(Vec::new()), Err(BoggleError::InvalidTile(_))));
+        for face in ["1", "?", "A B", "Quuu"] {
+            assert!(matches!(Die::new(vec!["A".to_string(), face.to_string()]), Err(BoggleError::InvalidTile(_))), "{face} is accepted");
+        }
+        assert!(Die::new(vec!["Qu".to_string(), String::new()]).is_ok());
+        assert!(matches!(DiceSet::parse("AAEEGN\nABBJOO", 2, 2), Err(BoggleError::InvalidDimensions { cells: 2, .. })));
+        assert!(matches!(DiceSet::from_file("does-not-exist.txt", 4, 4), Err(BoggleError::Io(_))));
+    }
+
+    #[test]
+    fn parses_a_dice_set_from_text(){
+        let set = DiceSet::parse("// tiny set\nAB\n\nCD\nEF\nGH\n", 2, 2).unwrap();
+        assert_eq!(set.dice().len(), 4);
+        assert_eq!(set.dice()[3].faces(), ["G", "H"]);
+    }
+}
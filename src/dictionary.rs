@@ -0,0 +1,58 @@
+use std::fs::File;
+use std::io::{BufRead, ErrorKind, Read};
+use std::path::Path;
+#[cfg(test)]
+use word_trie::{trie::Trie, Dictionary};
+use crate::error::BoggleError;
+use crate::compiled_dictionary::CompiledDictionary;
+
+/// reads the lines of a word list, one word per line.
+///
+/// fails with [`BoggleError::DictionaryParse`] if a line is not valid UTF-8.
+pub(crate) fn read_lines<R: BufRead>(reader: R) -> Result<Vec<String>, BoggleError> {
+    reader.lines().enumerate().map(|(index, line)| line.map_err(|error| match error.kind() {
+        ErrorKind::InvalidData => BoggleError::DictionaryParse { line: index + 1, reason: error.to_string() },
+        _ => BoggleError::Io(error),
+    })).collect()
+}
+
+/// reads a trie from a word list, one word per line, see [`list_word`].
+///
+/// fails with [`BoggleError::DictionaryParse`] if a line is not valid UTF-8.
+#[cfg(test)]
+pub(crate) fn read_trie<R: BufRead>(reader: R) -> Result<Trie, BoggleError> {
+    let mut trie = Trie::default();
+    for word in read_lines(reader)?.iter().filter_map(|line| list_word(line)) {
+        trie.add_word(&word);
+    }
+
+    Ok(trie)
+}
+
+/// gets a word of a word list as the board searches walk it, trimmed and in lower case. `None` for an empty word
+pub(crate) fn list_word(word: &str) -> Option<String> {
+    let word = word.trim();
+    (!word.is_empty()).then(|| word.to_ascii_lowercase())
+}
+
+/// loads a dictionary file for the board searches, a compiled dictionary as it is
+/// or a word list, one word per line, which is compiled.
+///
+/// fails with [`BoggleError::DictionaryParse`] if a line of a word list is not valid UTF-8.
+pub(crate) fn load_dictionary<P: AsRef<Path>>(path: P) -> Result<CompiledDictionary, BoggleError> {
+    read_dictionary(File::open(path)?)
+}
+
+/// reads a dictionary for the board searches to its end, a compiled dictionary as it is
+/// or a word list, one word per line, which is compiled.
+///
+/// fails with [`BoggleError::DictionaryParse`] if a line of a word list is not valid UTF-8.
+pub(crate) fn read_dictionary<R: Read>(mut reader: R) -> Result<CompiledDictionary, BoggleError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    if CompiledDictionary::is_compiled(&bytes) {
+        return CompiledDictionary::from_bytes(&bytes);
+    }
+
+    Ok(CompiledDictionary::from_words(read_lines(bytes.as_slice())?))
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::io;
+
+/// The boggle-maker error enum, returned by the crate's public entry points.
+#[derive(Debug)]
+pub enum BoggleError {
+    /// reading a dictionary or dice file failed
+    Io(io::Error),
+    /// the board dimensions are not usable, or do not fit the number of cells given
+    InvalidDimensions {
+        width: usize,
+        length: usize,
+        cells: usize,
+    },
+    /// a tile, die face or alphabet is not valid
+    InvalidTile(String),
+    /// the dictionary content could not be parsed
+    DictionaryParse {
+        line: usize,
+        reason: String,
+    },
+    /// the compiled dictionary file is damaged or not a compiled dictionary
+    InvalidDictionary(String),
+    /// the compiled dictionary file was written in another version of the format, it needs to be compiled again
+    DictionaryVersion {
+        found: u32,
+        expected: u32,
+    },
+    /// the operation needs a dictionary but none has been loaded
+    MissingDictionary,
+    /// a generator setting is out of its valid range
+    InvalidConfig(String),
+}
+
+impl BoggleError {
+    /// checks that a board of `width` columns and `length` rows can be searched and holds exactly `cells` cells
+    pub(crate) fn check_board(width: usize, length: usize, cells: usize) -> Result<(), Self> {
+        // the dfs paths store cell indexes as u16
+        let max_cells = u16::MAX as usize + 1;
+        if width == 0 || length == 0 || width.saturating_mul(length) != cells || cells > max_cells {
+            return Err(Self::InvalidDimensions { width, length, cells });
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for BoggleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "i/o error: {error}"),
+            Self::InvalidDimensions { width, length, cells } => {
+                write!(f, "invalid board dimensions: width {width} and length {length} for {cells} cells")
+            }
+            Self::InvalidTile(reason) => write!(f, "invalid tile: {reason}"),
+            Self::DictionaryParse { line, reason } => write!(f, "failed to parse dictionary line {line}: {reason}"),
+            Self::InvalidDictionary(reason) => write!(f, "invalid compiled dictionary: {reason}"),
+            Self::DictionaryVersion { found, expected } => {
+                write!(f, "compiled dictionary format version {found} is not supported, expected version {expected}; compile the word list again")
+            }
+            Self::MissingDictionary => write!(f, "no dictionary has been loaded"),
+            Self::InvalidConfig(reason) => write!(f, "invalid configuration: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for BoggleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BoggleError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
@@ -0,0 +1,605 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use crate::compiled_dictionary::CompiledDictionary;
+use crate::boggle_dictionary::BoggleDictionary;
+use crate::boggle_board::Board;
+use crate::error::BoggleError;
+use crate::scoring::{ScoringRules, ClassicScoring};
+use crate::tile::{Tile, default_alphabet};
+use crate::boggle_dfs::{traced_letters, find_words};
+
+/// the fitness cost of a required word's letter which can not be traced on the board
+const MISSING_LETTER_PENALTY: isize = 1 << 24;
+
+/// the fitness cost of a blocklisted word which can be traced on the board
+const BLOCKLISTED_WORD_PENALTY: isize = 1 << 24;
+
+/// The window a generated board's total score should fall in, both ends inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetScore {
+    min: isize,
+    max: isize,
+}
+
+impl TargetScore {
+    /// a target met by any score of at least `min`
+    pub fn at_least(min: isize) -> Self {
+        Self { min, max: isize::MAX }
+    }
+
+    /// a target met by any score between `min` and `max`
+    pub fn between(min: isize, max: isize) -> Self {
+        Self { min: min.min(max), max: max.max(min) }
+    }
+
+    /// gets the window's lowest score
+    pub fn min(&self) -> isize {
+        self.min
+    }
+
+    /// gets the window's highest score
+    pub fn max(&self) -> isize {
+        self.max
+    }
+
+    /// whether the score is in the window
+    pub fn contains(&self, score: isize) -> bool {
+        (self.min..=self.max).contains(&score)
+    }
+
+    /// gets how far the score is from the window, zero if the score is in it
+    pub fn distance(&self, score: isize) -> isize {
+        if score < self.min {
+            self.min - score
+        } else if score > self.max {
+            score - self.max
+        } else {
+            0
+        }
+    }
+}
+
+impl Default for TargetScore {
+    fn default() -> Self {
+        Self::at_least(0)
+    }
+}
+
+/// The progress of a board generation, passed to the progress callback on every generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    run: usize,
+    generation: usize,
+    best_score: Option<isize>,
+    elapsed: Duration,
+}
+
+impl Progress {
+    /// initiate a new progress
+    pub fn new(run: usize, generation: usize, best_score: Option<isize>, elapsed: Duration) -> Self {
+        Self { run, generation, best_score, elapsed }
+    }
+
+    /// gets the index of the current run, a generator may evolve boards in several runs
+    pub fn run(&self) -> usize {
+        self.run
+    }
+
+    /// gets the current generation of the current run
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// gets the score of the best board of the current run, if any has been scored
+    pub fn best_score(&self) -> Option<isize> {
+        self.best_score
+    }
+
+    /// gets the time since the generation started
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+/// The cancellation token struct, cancelling every generation it is given to.
+///
+/// a cancelled generation returns the best board found so far. clones share the same token.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// initiate a new token which is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// cancels the generations using this token
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// whether the token is cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The progress callback type
+pub type ProgressCallback = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+/// The generation context struct, holding the dictionary and every setting a generator needs to make a board.
+///
+/// the boards are scored on a [`CompiledDictionary`] unless the context is made on another [`BoggleDictionary`].
+pub struct GenerationContext<'a, D: BoggleDictionary = CompiledDictionary> {
+    dictionary: &'a D,
+    width: usize,
+    length: usize,
+    target: TargetScore,
+    alphabet: Vec<Tile>,
+    blocked_cells: usize,
+    scoring: Arc<dyn ScoringRules>,
+    seed: Option<u64>,
+    required_words: Vec<String>,
+    blocklist: Option<&'a D>,
+    locked_cells: Vec<(usize, usize, Tile)>,
+    progress: Option<ProgressCallback>,
+    cancellation: Option<CancellationToken>,
+    time_budget: Option<Duration>,
+}
+
+impl<'a, D: BoggleDictionary> GenerationContext<'a, D> {
+    /// initiate a new generation context for a board of `width` columns and `length` rows,
+    /// using the `A` to `Z` alphabet and the classic scoring.
+    pub fn new(dictionary: &'a D, width: usize, length: usize) -> Self {
+        Self {
+            dictionary,
+            width,
+            length,
+            target: TargetScore::default(),
+            alphabet: default_alphabet(),
+            blocked_cells: 0,
+            scoring: Arc::new(ClassicScoring),
+            seed: None,
+            required_words: Vec::new(),
+            blocklist: None,
+            locked_cells: Vec::new(),
+            progress: None,
+            cancellation: None,
+            time_budget: None,
+        }
+    }
+
+    /// sets the target score window
+    pub fn with_target(mut self, target: TargetScore) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// sets the tiles the board is generated from
+    pub fn with_alphabet(mut self, alphabet: Vec<Tile>) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// sets the number of cells to be blocked
+    pub fn with_blocked_cells(mut self, count: usize) -> Self {
+        self.blocked_cells = count;
+        self
+    }
+
+    /// sets the scoring rules the target is measured by
+    pub fn with_scoring(mut self, scoring: Arc<dyn ScoringRules>) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// sets the seed of the random choices, the same seed and settings always give the same board
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// sets the words every generated board must contain
+    pub fn with_required_words(mut self, words: Vec<String>) -> Self {
+        self.required_words = words;
+        self
+    }
+
+    /// sets the dictionary of words no generated board may contain
+    pub fn with_blocklist(mut self, blocklist: &'a D) -> Self {
+        self.blocklist = Some(blocklist);
+        self
+    }
+
+    /// pins the tile at the cell in the given row and column, the generators never change a locked cell
+    pub fn with_locked_cell(mut self, row: usize, column: usize, tile: Tile) -> Self {
+        self.locked_cells.push((row, column, tile));
+        self
+    }
+
+    /// sets the callback called with the progress on every generation
+    pub fn with_progress(mut self, progress: ProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// sets the token cancelling the generation
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// sets the wall-clock time after which the generation stops with the best board found so far
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// gets the dictionary used to find the words
+    pub fn dictionary(&self) -> &'a D {
+        self.dictionary
+    }
+
+    /// gets the board's width, the number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// gets the board's length, the number of rows
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// gets the target score window
+    pub fn target(&self) -> TargetScore {
+        self.target
+    }
+
+    /// gets the tiles the board is generated from
+    pub fn alphabet(&self) -> &[Tile] {
+        &self.alphabet
+    }
+
+    /// gets the number of cells to be blocked
+    pub fn blocked_cells(&self) -> usize {
+        self.blocked_cells
+    }
+
+    /// gets the scoring rules
+    pub fn scoring(&self) -> &Arc<dyn ScoringRules> {
+        &self.scoring
+    }
+
+    /// gets the seed of the random choices, if any
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// gets the words every generated board must contain
+    pub fn required_words(&self) -> &[String] {
+        &self.required_words
+    }
+
+    /// gets the number of letters of the required words which can not be traced on the board,
+    /// a word's letters after its longest prefix spelled by a path of whole tiles are counted as missing.
+    ///
+    /// zero means every required word can be traced. fails if the board does not fit the context's width and length
+    pub fn missing_letters(&self, board: &[Tile]) -> Result<usize, BoggleError> {
+        let mut missing = 0;
+        for word in &self.required_words {
+            //a traced word's longest prefix is the whole word
+            missing += word.chars().count() - traced_letters(board, self.width, self.length, word)?;
+        }
+
+        Ok(missing)
+    }
+
+    /// gets the dictionary of words no generated board may contain, if any
+    pub fn blocklist(&self) -> Option<&'a D> {
+        self.blocklist
+    }
+
+    /// gets the locked cells as row, column and tile, a later lock of the same cell wins
+    pub fn locked_cells(&self) -> &[(usize, usize, Tile)] {
+        &self.locked_cells
+    }
+
+    /// gets the token cancelling the generation, if any
+    pub fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// gets the wall-clock time budget of the generation, if any
+    pub fn time_budget(&self) -> Option<Duration> {
+        self.time_budget
+    }
+
+    /// calls the progress callback, if any
+    pub(crate) fn report(&self, progress: &Progress) {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+    }
+
+    /// whether a generation started at the given time must stop, as it is cancelled or out of its time budget
+    pub(crate) fn should_stop(&self, started: Instant) -> bool {
+        self.cancellation.as_ref().is_some_and(CancellationToken::is_cancelled)
+            || self.time_budget.is_some_and(|budget| started.elapsed() >= budget)
+    }
+
+    /// gets every blocklisted word which can be traced on the board, in upper case.
+    /// fails if the board does not fit the context's width and length
+    pub fn blocklisted_words(&self, board: &[Tile]) -> Result<HashSet<String>, BoggleError> {
+        match self.blocklist {
+            Some(blocklist) => find_words(blocklist, board, self.width, self.length),
+            None => BoggleError::check_board(self.width, self.length, board.len()).map(|_| HashSet::new()),
+        }
+    }
+
+    /// gets the fitness of a board with the given score, zero for a board in the target window which has every required word
+    /// and no blocklisted word, negative otherwise.
+    ///
+    /// the closer to the target window the better, a missing letter of a required word or a blocklisted word outweighs any distance to it.
+    pub(crate) fn fitness(&self, score: u32, board: &[Tile]) -> isize {
+        let distance = self.target.distance(score as isize);
+        let missing = self.missing_letters(board).expect("a generated board fits the context") as isize;
+        let blocklisted = self.blocklisted_words(board).expect("a generated board fits the context").len() as isize;
+        -distance
+            .saturating_add(missing.saturating_mul(MISSING_LETTER_PENALTY))
+            .saturating_add(blocklisted.saturating_mul(BLOCKLISTED_WORD_PENALTY))
+    }
+
+    /// whether every required word and no blocklisted word can be traced on the board
+    pub(crate) fn accepts(&self, board: &[Tile]) -> bool {
+        self.missing_letters(board).is_ok_and(|missing| missing == 0)
+            && self.blocklisted_words(board).is_ok_and(|words| words.is_empty())
+    }
+
+    /// checks the dimensions are valid, the alphabet is not empty, the blocked cells fit on the cells which are not locked
+    /// and the required words are words of the dictionary long enough to count
+    pub(crate) fn validate(&self) -> Result<(), BoggleError> {
+        BoggleError::check_board(self.width, self.length, self.width.saturating_mul(self.length))?;
+        if self.alphabet.is_empty() {
+            return Err(BoggleError::InvalidTile("the alphabet is empty".to_string()));
+        }
+        if let Some((row, column, _)) = self.locked_cells.iter().find(|(row, column, _)| *row >= self.length || *column >= self.width) {
+            return Err(BoggleError::InvalidConfig(format!("the locked cell at row {row} and column {column} is outside the board")));
+        }
+        let locked: HashSet<(usize, usize)> = self.locked_cells.iter().map(|&(row, column, _)| (row, column)).collect();
+        let free = self.width * self.length - locked.len();
+        if self.blocked_cells > free {
+            return Err(BoggleError::InvalidConfig(format!("{} cells can not be blocked, only {free} cells are not locked", self.blocked_cells)));
+        }
+        if let Some(word) = self.required_words.iter().find(|word| word.is_empty() || !word.chars().all(char::is_alphabetic)) {
+            return Err(BoggleError::InvalidConfig(format!("the required word '{word}' is not made of letters")));
+        }
+        let min_length = self.scoring.min_word_length();
+        if let Some(word) = self.required_words.iter().find(|word| word.chars().count() < min_length) {
+            return Err(BoggleError::InvalidConfig(format!("the required word '{word}' has fewer than {min_length} letters")));
+        }
+        //the searches only find the dictionary's words, so a word missing from it would never be found on the board
+        if let Some(word) = self.required_words.iter().find(|word| !self.dictionary.contains(&word.to_ascii_lowercase())) {
+            return Err(BoggleError::InvalidConfig(format!("the required word '{word}' is not in the dictionary")));
+        }
+
+        Ok(())
+    }
+
+    /// gets the random number generator of a generation, seeded by the context's seed if any
+    pub(crate) fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        }
+    }
+}
+
+impl<D: BoggleDictionary> Clone for GenerationContext<'_, D> {
+    fn clone(&self) -> Self {
+        Self {
+            dictionary: self.dictionary,
+            width: self.width,
+            length: self.length,
+            target: self.target,
+            alphabet: self.alphabet.clone(),
+            blocked_cells: self.blocked_cells,
+            scoring: self.scoring.clone(),
+            seed: self.seed,
+            required_words: self.required_words.clone(),
+            blocklist: self.blocklist,
+            locked_cells: self.locked_cells.clone(),
+            progress: self.progress.clone(),
+            cancellation: self.cancellation.clone(),
+            time_budget: self.time_budget,
+        }
+    }
+}
+
+impl<D: BoggleDictionary> fmt::Debug for GenerationContext<'_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GenerationContext")
+            .field("width", &self.width)
+            .field("length", &self.length)
+            .field("target", &self.target)
+            .field("alphabet", &self.alphabet)
+            .field("blocked_cells", &self.blocked_cells)
+            .field("scoring", &self.scoring)
+            .field("seed", &self.seed)
+            .field("required_words", &self.required_words)
+            .field("locked_cells", &self.locked_cells)
+            .field("cancellation", &self.cancellation)
+            .field("time_budget", &self.time_budget)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The cells of the board which are not generated, the locked cells and the blocked cells.
+#[derive(Clone, Debug)]
+pub(crate) struct BoardLayout(Vec<Option<Tile>>);
+
+impl BoardLayout {
+    /// a layout of the context's locked cells and its number of blocked cells placed at random on the other cells
+    pub(crate) fn new<D: BoggleDictionary, R: Rng + ?Sized>(context: &GenerationContext<D>, rng: &mut R) -> Self {
+        let mut layout = vec![None; context.width() * context.length()];
+        for &(row, column, tile) in context.locked_cells() {
+            layout[row * context.width() + column] = Some(tile);
+        }
+
+        let mut positions: Vec<usize> = (0..layout.len()).filter(|&position| layout[position].is_none()).collect();
+        positions.shuffle(rng);
+        for position in positions.into_iter().take(context.blocked_cells()) {
+            layout[position] = Some(Tile::BLOCKED);
+        }
+
+        Self(layout)
+    }
+
+    /// a layout of `cells` cells which are all generated
+    pub(crate) fn free(cells: usize) -> Self {
+        Self(vec![None; cells])
+    }
+
+    /// the number of cells to be generated
+    pub(crate) fn free_cells(&self) -> usize {
+        self.0.iter().filter(|cell| cell.is_none()).count()
+    }
+
+    /// gets the indexes of the cells to be generated
+    pub(crate) fn free_indexes(&self) -> Vec<usize> {
+        (0..self.0.len()).filter(|&index| self.0[index].is_none()).collect()
+    }
+
+    /// gets a full board with random tiles of the alphabet on the free cells
+    pub(crate) fn random_board<R: Rng + ?Sized>(&self, alphabet: &[Tile], rng: &mut R) -> Vec<Tile> {
+        let genes: Vec<Tile> = (0..self.free_cells()).map(|_| alphabet[rng.random_range(0..alphabet.len())]).collect();
+        self.board(&genes)
+    }
+
+    /// gets the cell indexes and tiles of the cells which are not generated
+    pub(crate) fn fixed_cells(&self) -> impl Iterator<Item = (usize, Tile)> + '_ {
+        self.0.iter().enumerate().filter_map(|(index, cell)| cell.map(|tile| (index, tile)))
+    }
+
+    /// gets the full board by filling the free cells with the genes in order
+    pub(crate) fn board(&self, genes: &[Tile]) -> Vec<Tile> {
+        let mut genes = genes.iter();
+        self.0
+            .iter()
+            .map(|cell| cell.unwrap_or_else(|| *genes.next().expect("a gene for every free cell")))
+            .collect()
+    }
+}
+
+/// A board generation engine, picked by `BoggleBuilder::with_strategy`.
+///
+/// every strategy honours the context's target window, seed, locked and blocked cells, required words, blocklist,
+/// progress callback, cancellation token and time budget.
+pub trait GenerationStrategy<D: BoggleDictionary = CompiledDictionary>: fmt::Debug + Send + Sync {
+    /// generates a board by the given context, `None` if no board with every required word and no blocklisted word is found
+    fn generate(&self, context: &GenerationContext<D>) -> Result<Option<GenerationReport>, BoggleError>;
+}
+
+/// The generation report struct, holding the generated board and whether it met the target.
+#[derive(Debug)]
+pub struct GenerationReport {
+    board: Board,
+    target_hit: bool,
+}
+
+impl GenerationReport {
+    /// initiate a new report for a board which score is set
+    pub fn new(board: Board, target: TargetScore) -> Self {
+        let target_hit = board.score().is_some_and(|score| target.contains(score));
+        Self { board, target_hit }
+    }
+
+    /// gets the generated board
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// takes the generated board out of the report
+    pub fn into_board(self) -> Board {
+        self.board
+    }
+
+    /// gets the generated board's score
+    pub fn score(&self) -> isize {
+        self.board.score().unwrap_or_default()
+    }
+
+    /// whether the generated board's score is in the target window
+    pub fn target_hit(&self) -> bool {
+        self.target_hit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_the_distance_to_the_window(){
+        let target = TargetScore::between(2600, 2400);
+        assert_eq!((target.min(), target.max()), (2400, 2600));
+        assert_eq!(target.distance(2300), 100);
+        assert_eq!(target.distance(2500), 0);
+        assert_eq!(target.distance(4000), 1400);
+        assert!(target.contains(2600) && !target.contains(2601));
+
+        let target = TargetScore::at_least(2500);
+        assert_eq!(target.distance(4000), 0);
+        assert_eq!(target.distance(2000), 500);
+    }
+
+    #[test]
+    fn counts_the_missing_letters_of_the_required_words(){
+        let dictionary = CompiledDictionary::default();
+        let board: Vec<Tile> = "SERSPATGLINESERS".chars().map(Tile::from).collect();
+        let context = GenerationContext::new(&dictionary, 4, 4)
+            .with_required_words(vec!["spat".to_string(), "Lines".to_string()]);
+        assert_eq!(context.missing_letters(&board).unwrap(), 0);
+
+        //only "PAT" of "PATH" and nothing of "ZOO" can be traced
+        let context = context.with_required_words(vec!["path".to_string(), "zoo".to_string()]);
+        assert_eq!(context.missing_letters(&board).unwrap(), 4);
+
+        //a multi-letter tile is matched as a whole, "Q" alone can never be traced on a "Qu" tile
+        let board = vec![
+            Tile::new("Qu").unwrap(), Tile::from('I'), Tile::from('X'),
+            Tile::from('X'), Tile::from('E'), Tile::from('X'),
+            Tile::from('X'), Tile::from('T'), Tile::from('X'),
+        ];
+        let context = GenerationContext::new(&dictionary, 3, 3).with_required_words(vec!["quiet".to_string()]);
+        assert_eq!(context.missing_letters(&board).unwrap(), 0);
+        let context = context.with_required_words(vec!["quilt".to_string(), "quo".to_string()]);
+        assert_eq!(context.missing_letters(&board).unwrap(), 3);
+    }
+
+    #[test]
+    fn rejects_more_blocked_cells_than_free_cells(){
+        let dictionary = CompiledDictionary::default();
+        let context = GenerationContext::new(&dictionary, 2, 2)
+            .with_locked_cell(0, 0, Tile::from('A'))
+            .with_locked_cell(0, 0, Tile::from('B'));
+        assert!(context.clone().with_blocked_cells(3).validate().is_ok());
+        assert!(matches!(context.with_blocked_cells(4).validate(), Err(BoggleError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn rejects_required_words_the_searches_never_find(){
+        let dictionary = CompiledDictionary::from_words(["cat", "quiet", "at"]);
+        let context = GenerationContext::new(&dictionary, 4, 4);
+        assert!(context.clone().with_required_words(vec!["Quiet".to_string(), "cat".to_string()]).validate().is_ok());
+        for word in ["dog", "at", "it's"] {
+            let result = context.clone().with_required_words(vec![word.to_string()]).validate();
+            assert!(matches!(result, Err(BoggleError::InvalidConfig(_))), "{word} is accepted");
+        }
+    }
+}
@@ -1,30 +1,131 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 use genetic_algorithm::strategy::evolve::prelude::*;
-use word_trie::trie::Trie;
+use rand::Rng;
 use crate::boggle_board::Board;
-use crate::total_boggle_score_calculator::TotalBoggleScoreCalculator;
+use crate::tile::Tile;
+use crate::boggle_dictionary::BoggleDictionary;
+use crate::generation::{GenerationContext, GenerationReport, GenerationStrategy, BoardLayout, Progress};
+use crate::genetic_config::GeneticConfig;
+use crate::error::BoggleError;
+use crate::total_boggle_score_calculator::{IncrementalScoreCalculator, TotalBoggleScoreCalculator};
 
 
-#[derive(Clone, Debug)]
-struct BoggleFitness<'a> {
-    score_calc: TotalBoggleScoreCalculator<'a>,
+impl Allele for Tile {}
+
+/// the most cells a board may differ in from a recently scored board to be scored by changing that board's cells,
+/// each changed cell costs about half a full search
+const MAX_CHANGED_CELLS: usize = 2;
+
+/// the most cells of a board whose paths are kept by an incremental calculator, a larger board is searched again on every change
+const MAX_INCREMENTAL_CELLS: usize = 64;
+
+/// The fitness of the boards, cloned for every thread scoring them in parallel.
+///
+/// the clones borrow the same dictionary, which is only read while the boards are scored.
+/// each clone keeps the paths of the boards it scored last, so a child equal to one of them, or differing from it
+/// in a cell or two, is scored by changing only those cells.
+struct BoggleFitness<'a, D: BoggleDictionary> {
+    score_calc: TotalBoggleScoreCalculator<'a, D>,
+    layout: BoardLayout,
+    context: GenerationContext<'a, D>,
+    stopped: Arc<AtomicBool>,
+    recent: VecDeque<IncrementalScoreCalculator<'a, D>>,
+    recent_capacity: usize,
 }
 
-impl <'a> BoggleFitness<'a> {
-    pub fn new(dictionary : &'a Trie, x_size:usize, y_size:usize)->Self{
-        let score_calc = TotalBoggleScoreCalculator::new(dictionary, x_size, y_size);
+impl <'a, D: BoggleDictionary> BoggleFitness<'a, D> {
+    pub fn new(context: &GenerationContext<'a, D>, layout: BoardLayout, stopped: Arc<AtomicBool>)->Self{
+        let score_calc = TotalBoggleScoreCalculator::new(context.dictionary(), context.width(), context.length())
+            .with_scoring(context.scoring().clone());
 
         Self{
-            score_calc
+            score_calc,
+            layout,
+            context: context.clone(),
+            stopped,
+            recent: VecDeque::new(),
+            recent_capacity: 0,
+        }
+    }
+
+    /// keeps the paths of up to `capacity` recently scored boards, none on a board too large to be scored incrementally
+    pub fn with_recent_boards(mut self, capacity: usize) -> Self {
+        if self.context.width() * self.context.length() <= MAX_INCREMENTAL_CELLS {
+            self.recent_capacity = capacity;
+        }
+        self
+    }
+
+    fn get_board_score(&mut self,genes:&[Tile]) -> Option<u32>{         
+        self.score_calc.score(&self.layout.board(genes)).ok()
+    }
+
+    /// scores the board, from the closest recently scored board if it differs in at most `MAX_CHANGED_CELLS` cells
+    fn score_board(&mut self, board: &[Tile]) -> Option<u32> {
+        if self.recent_capacity == 0 {
+            return self.score_calc.score(board).ok();
+        }
+
+        let closest = self.recent.iter()
+            .enumerate()
+            .map(|(index, recent)| (changed_cells(recent.board(), board).count(), index))
+            .min();
+        let calculator = match closest {
+            Some((0, index)) => return Some(self.recent[index].score()),
+            Some((changed, index)) if changed <= MAX_CHANGED_CELLS => {
+                let mut calculator = self.recent[index].clone();
+                for cell in changed_cells(self.recent[index].board(), board) {
+                    calculator.set(cell, board[cell]);
+                }
+                calculator
+            }
+            _ => self.score_calc.incremental(board).ok()?,
+        };
+
+        let score = calculator.score();
+        if self.recent.len() == self.recent_capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(calculator);
+        Some(score)
+    }
+}
+
+/// gets the indexes of the cells holding different tiles on the boards
+fn changed_cells<'b>(a: &'b [Tile], b: &'b [Tile]) -> impl Iterator<Item = usize> + 'b {
+    a.iter().zip(b).enumerate().filter(|(_, (a, b))| a != b).map(|(cell, _)| cell)
+}
+
+impl<D: BoggleDictionary> Clone for BoggleFitness<'_, D> {
+    fn clone(&self) -> Self {
+        //a clone scores boards of its own, it starts with no recent board
+        Self {
+            score_calc: self.score_calc.clone(),
+            layout: self.layout.clone(),
+            context: self.context.clone(),
+            stopped: self.stopped.clone(),
+            recent: VecDeque::new(),
+            recent_capacity: self.recent_capacity,
         }
     }
+}
 
-    fn get_board_score(&mut self,board:&Vec<char>) -> u32{         
-        self.score_calc.score(board)
+impl<D: BoggleDictionary> fmt::Debug for BoggleFitness<'_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoggleFitness")
+            .field("layout", &self.layout)
+            .field("context", &self.context)
+            .field("stopped", &self.stopped)
+            .finish_non_exhaustive()
     }
 }
 
-impl Fitness for BoggleFitness<'_> {
-    type Genotype = ListGenotype<char>;
+impl<D: BoggleDictionary + Sync> Fitness for BoggleFitness<'_, D> {
+    type Genotype = ListGenotype<Tile>;
 
     fn calculate_for_chromosome(
         &mut self,
@@ -32,59 +133,252 @@ impl Fitness for BoggleFitness<'_> {
         _genotype: &FitnessGenotype<Self>,
     ) -> Option<FitnessValue> {
 
-        let score = self.get_board_score(&chromosome.genes);
-        Some(score as FitnessValue)
+        //a stopped run does not score its new boards, so none of them can become the best one
+        if self.stopped.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        //every board in the target window is a perfect fit
+        let board = self.layout.board(&chromosome.genes);
+        let score = self.score_board(&board)?;
+        Some(self.context.fitness(score, &board))
     }
+}
 
-    
+/// The reporter of a run, calling the context's progress callback on every generation and stopping the run
+/// once it passes the maximum number of generations, is cancelled or is out of its time budget.
+///
+/// a stopped run's new boards are not scored, so the run ends when it runs out of stale generations.
+struct GenerationReporter<'a, D: BoggleDictionary> {
+    context: GenerationContext<'a, D>,
+    fitness: BoggleFitness<'a, D>,
+    max_generations: Option<usize>,
+    stopped: Arc<AtomicBool>,
+    started: Instant,
+    run: usize,
+    best_score: Option<isize>,
 }
 
-///generates a boggle board instance by given width, length, target score 
-/// and a trie to be used for calculation of the scores
-///this method is using genetic algorithm for finding the best fit for the target score
-pub fn make(width : usize, length : usize, target_score: isize , dictionary: &Trie) 
--> Option<Board> {
-    let allele_lists = vec![
-        'a', 'b', 'c', 'd', 'e', 
-        'f', 'g', 'h', 'i', 'j', 
-        'k', 'l', 'm', 'n', 'o',
-        'p', 'q', 'r', 's', 't', 
-        'u', 'v', 'w', 'x', 'y', 
-        'z',
-    ];
+impl<D: BoggleDictionary> Clone for GenerationReporter<'_, D> {
+    fn clone(&self) -> Self {
+        Self {
+            context: self.context.clone(),
+            fitness: self.fitness.clone(),
+            max_generations: self.max_generations,
+            stopped: self.stopped.clone(),
+            started: self.started,
+            run: self.run,
+            best_score: self.best_score,
+        }
+    }
+}
+
+impl<D: BoggleDictionary> GenerationReporter<'_, D> {
+    /// the same reporter for the run of the given index
+    fn for_run(&self, run: usize) -> Self {
+        Self { run, ..self.clone() }
+    }
+}
+
+impl<D: BoggleDictionary + Sync> StrategyReporter for GenerationReporter<'_, D> {
+    type Genotype = ListGenotype<Tile>;
+
+    fn on_enter<S: StrategyState<Self::Genotype>, C: StrategyConfig>(&mut self, _genotype: &Self::Genotype, _state: &S, _config: &C) {
+        //every run starts over, the runs are called one after the other
+        self.stopped.store(false, Ordering::Relaxed);
+        self.best_score = None;
+    }
+
+    fn on_new_generation<S: StrategyState<Self::Genotype>, C: StrategyConfig>(&mut self, _genotype: &Self::Genotype, state: &S, _config: &C) {
+        if self.max_generations.is_some_and(|max| state.current_generation() > max) || self.context.should_stop(self.started) {
+            self.stopped.store(true, Ordering::Relaxed);
+        }
+        self.context.report(&Progress::new(self.run, state.current_generation(), self.best_score, self.started.elapsed()));
+    }
+
+    fn on_new_best_chromosome<S: StrategyState<Self::Genotype>, C: StrategyConfig>(&mut self, genotype: &Self::Genotype, _state: &S, _config: &C) {
+        self.best_score = self.fitness.get_board_score(genotype.best_genes()).map(|score| score as isize);
+    }
+}
+
+/// The generation strategy evolving the boards with the `genetic_algorithm` crate, tuned by a genetic config.
+#[derive(Debug, Clone, Default)]
+pub struct GeneticStrategy {
+    config: GeneticConfig,
+}
+
+impl GeneticStrategy {
+    /// initiate a new strategy using the given genetic config
+    pub fn new(config: GeneticConfig) -> Self {
+        Self { config }
+    }
+
+    /// gets the genetic config
+    pub fn config(&self) -> &GeneticConfig {
+        &self.config
+    }
+}
+
+impl<D: BoggleDictionary + Sync> GenerationStrategy<D> for GeneticStrategy {
+    fn generate(&self, context: &GenerationContext<D>) -> Result<Option<GenerationReport>, BoggleError> {
+        make(context, &self.config)
+    }
+}
+
+///generates a boggle board instance by the given generation context, holding the board's dimensions, the target score window,
+/// the alphabet of tiles to build the board from, the number of cells to block, the scoring rules and a trie to be used for calculation of the scores
+///this method is using genetic algorithm, tuned by the given config, for finding the board closest to the target score window
+///
+///every required word and no blocklisted word of the context can be traced on the generated board,
+///`None` is returned if no such board is found.
+///the same seed in the context, with the same settings and dictionary, always gives the same board.
+///the context's progress callback is called on every generation, and the best board found so far is returned
+///once the context's cancellation token is cancelled or its time budget is spent.
+///
+///fails if the dimensions are not valid, the alphabet is empty or the config is out of range, returns `None` if every cell is locked or blocked
+pub fn make<D: BoggleDictionary + Sync>(context: &GenerationContext<D>, config: &GeneticConfig) -> Result<Option<GenerationReport>, BoggleError> {
+    context.validate()?;
+    config.validate()?;
+    let (width, length) = (context.width(), context.length());
+
+    //every random choice, including the seed of each run, is drawn from this generator
+    let mut rng = context.rng();
+
+    //the locked and blocked cells are not genes, so they are never mutated or crossed over
+    let layout = BoardLayout::new(context, &mut rng);
+    if layout.free_cells() == 0 {
+        return Ok(None);
+    }
 
     //define the geno type
     let genotype = ListGenotype::builder()
-     .with_genes_size(width * length)
-     .with_allele_list(allele_lists)
+     .with_genes_size(layout.free_cells())
+     .with_allele_list(context.alphabet().to_vec())
      .with_genes_hashing(false) // optional, defaults to false
      .build()
-     .unwrap();
-
-     let evolve_builder = Evolve::builder()
-        .with_genotype(genotype)
-        .with_target_population_size(100)
-        .with_max_stale_generations(25)
-        .with_mutate(MutateSingleGene::new(0.2))
-        .with_crossover(CrossoverUniform::new())
-        .with_select(SelectElite::new(0.8))
-        .with_extension(ExtensionMassDegeneration::new(2, 10))
-         .with_reporter(EvolveReporterSimple::new(50))
-        // .with_par_fitness(true)
-        .with_target_fitness_score(target_score)
-        //.with_select(SelectTournament::new(4, 0.9))
-        //.with_mutate(MutateMultiGeneDynamic::new(2, 0.1, 250))
-        //.with_reporter(EvolveReporterDuration::new())
-        .with_fitness(BoggleFitness::new(dictionary,width,length));
-
-    let (evolve, _) = evolve_builder.call_speciated(10).unwrap();
-        if let Some(best_chromosome) = evolve.best_chromosome() {
-        let board = Board::new(best_chromosome.genes, width, length, best_chromosome.fitness_score);
-
-        Some(board)
-        //println!("{:?}",best_chromosome.genes);
+     .expect("the genes size and alphabet are validated");
+
+    let started = Instant::now();
+    let stopped = Arc::new(AtomicBool::new(false));
+    let mut fitness = BoggleFitness::new(context, layout.clone(), stopped.clone())
+        .with_recent_boards(config.population_size());
+    let reporter = GenerationReporter {
+        context: context.clone(),
+        fitness: fitness.clone(),
+        max_generations: config.max_generations(),
+        stopped,
+        started,
+        run: 0,
+        best_score: None,
+    };
+    let evolve_builder = Evolve::builder()
+        .with_genotype(genotype.clone())
+        .with_target_population_size(config.population_size())
+        .with_max_stale_generations(config.max_stale_generations())
+        .with_mutate(config.mutate_plugin())
+        .with_crossover(config.crossover_plugin())
+        .with_select(config.select_plugin())
+        .with_extension(config.extension_plugin())
+        .with_reporter(reporter.clone())
+        .with_target_fitness_score(0)
+        .with_par_fitness(config.parallel_fitness())
+        .with_fitness(fitness.clone());
+
+    //the speciated runs are called one by one, as each of them needs a seed of its own
+    let species = if config.speciation_runs() > 1 { config.speciation_runs() } else { 0 };
+    let mut best_genes_list = Vec::new();
+    let mut best_run = None;
+    let mut best_fitness = None;
+    let mut stopped_early = false;
+    for run in 0..species {
+        let species_run = evolve_builder
+            .clone()
+            .with_reporter(reporter.for_run(run))
+            .with_rng_seed_from_u64(rng.random())
+            .call()
+            .map_err(invalid_config)?;
+        best_genes_list.extend(species_run.best_genes());
+
+        let target_hit = species_run.best_fitness_score() == Some(0);
+        if best_run.is_none() || species_run.best_fitness_score() > best_fitness {
+            best_fitness = species_run.best_fitness_score();
+            best_run = Some(species_run);
+        }
+        //no final run is needed once the target is hit, or allowed once the generation is stopped
+        if target_hit || context.should_stop(started) {
+            stopped_early = true;
+            break;
+        }
+    }
+
+    let evolve = match best_run {
+        Some(species_run) if stopped_early => species_run,
+        _ => {
+            //the final run starts from the best boards of the speciated runs
+            let mut final_genotype = genotype;
+            final_genotype.set_seed_genes_list(best_genes_list);
+            evolve_builder
+                .with_genotype(final_genotype)
+                .with_reporter(reporter.for_run(species))
+                .with_rng_seed_from_u64(rng.random())
+                .call()
+                .map_err(invalid_config)?
+        }
+    };
+
+    if let Some(best_chromosome) = evolve.best_chromosome() {
+        let board = layout.board(&best_chromosome.genes);
+        if !context.accepts(&board) {
+            return Ok(None);
+        }
+
+        //the fitness is the distance to the window, the board keeps its actual score
+        let score = fitness.get_board_score(&best_chromosome.genes).map(|score| score as isize);
+        let board = Board::new(board, width, length, score);
+
+        Ok(Some(GenerationReport::new(board, context.target())))
     } else {
-        //println!("Invalid solution with fitness score: None");
-        None
+        Ok(None)
+    }
+}
+
+fn invalid_config(error: TryFromEvolveBuilderError) -> BoggleError {
+    BoggleError::InvalidConfig(error.0.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::dictionary::load_dictionary;
+    use crate::tile::default_alphabet;
+
+    #[test]
+    fn boards_close_to_recent_ones_keep_their_full_score(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let alphabet = default_alphabet();
+        let mut rng = StdRng::seed_from_u64(8);
+
+        for (width, length) in [(4, 4), (9, 8)] {
+            let context = GenerationContext::new(&dictionary, width, length);
+            let layout = BoardLayout::free(width * length);
+            let mut fitness = BoggleFitness::new(&context, layout.clone(), Arc::new(AtomicBool::new(false))).with_recent_boards(3);
+            let mut full = TotalBoggleScoreCalculator::new(&dictionary, width, length);
+
+            //the children are equal to, a cell or two away from or far from the boards scored before them
+            let mut board = layout.random_board(&alphabet, &mut rng);
+            for _ in 0..40 {
+                if rng.random_bool(0.2) {
+                    board = layout.random_board(&alphabet, &mut rng);
+                }
+                for _ in 0..rng.random_range(0..=MAX_CHANGED_CELLS + 1) {
+                    let cell = rng.random_range(0..board.len());
+                    board[cell] = alphabet[rng.random_range(0..alphabet.len())];
+                }
+                assert_eq!(fitness.score_board(&board), full.score(&board).ok());
+                assert!(fitness.recent.len() <= 3);
+            }
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,306 @@
+use genetic_algorithm::strategy::evolve::prelude::*;
+use crate::error::BoggleError;
+
+/// The way the genetic generator mutates the boards of a generation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeneticMutation {
+    /// changes a single random tile of a board with the given probability
+    SingleGene { probability: f32 },
+    /// changes up to `mutations` random tiles of a board with the given probability
+    MultiGene { mutations: usize, probability: f32 },
+}
+
+/// The way the genetic generator mixes two parent boards into a child board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneticCrossover {
+    /// takes every tile from either parent at random
+    Uniform,
+    /// swaps a single random tile between the parents
+    SingleGene,
+    /// swaps the tiles after a single random cell between the parents
+    SinglePoint,
+    /// swaps the given number of random tiles between the parents
+    MultiGene { genes: usize },
+    /// swaps the tiles between the given number of random cells between the parents
+    MultiPoint { points: usize },
+}
+
+/// The way the genetic generator picks the parent boards of the next generation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeneticSelection {
+    /// keeps the best `rate` part of the generation
+    Elite { rate: f32 },
+    /// keeps the best `rate` part of the generation, picked by tournaments of `size` boards
+    Tournament { size: usize, rate: f32 },
+}
+
+/// The way the genetic generator shakes up a generation whose boards have become too alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneticExtension {
+    /// leaves every generation as it is
+    Noop,
+    /// mutates every board `mutations` times once the generation has no more than `cardinality` distinct fitness scores
+    MassDegeneration { cardinality: usize, mutations: usize },
+}
+
+/// The genetic config struct, holding the genetic algorithm parameters used to evolve a board.
+///
+/// The default config is a balance of quality and latency, [`GeneticConfig::fast`] suits interactive
+/// requests and [`GeneticConfig::thorough`] suits batch jobs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneticConfig {
+    population_size: usize,
+    mutation: GeneticMutation,
+    crossover: GeneticCrossover,
+    selection: GeneticSelection,
+    extension: GeneticExtension,
+    max_stale_generations: usize,
+    speciation_runs: usize,
+    max_generations: Option<usize>,
+    parallel_fitness: bool,
+}
+
+impl GeneticConfig {
+    /// a config finding a board quickly, in a single short run with a small population
+    pub fn fast() -> Self {
+        Self {
+            population_size: 50,
+            max_stale_generations: 10,
+            speciation_runs: 1,
+            max_generations: Some(100),
+            ..Self::default()
+        }
+    }
+
+    /// a config searching longer for a board closer to the target, in many runs with a large population
+    pub fn thorough() -> Self {
+        Self {
+            population_size: 200,
+            selection: GeneticSelection::Tournament { size: 4, rate: 0.9 },
+            max_stale_generations: 50,
+            speciation_runs: 20,
+            ..Self::default()
+        }
+    }
+
+    /// sets the number of boards in every generation
+    pub fn with_population_size(mut self, size: usize) -> Self {
+        self.population_size = size;
+        self
+    }
+
+    /// sets the mutation strategy
+    pub fn with_mutation(mut self, mutation: GeneticMutation) -> Self {
+        self.mutation = mutation;
+        self
+    }
+
+    /// sets the crossover strategy
+    pub fn with_crossover(mut self, crossover: GeneticCrossover) -> Self {
+        self.crossover = crossover;
+        self
+    }
+
+    /// sets the selection strategy
+    pub fn with_selection(mut self, selection: GeneticSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// sets the extension shaking up a generation of boards too alike
+    pub fn with_extension(mut self, extension: GeneticExtension) -> Self {
+        self.extension = extension;
+        self
+    }
+
+    /// sets the number of generations without a better board after which a run stops
+    pub fn with_max_stale_generations(mut self, generations: usize) -> Self {
+        self.max_stale_generations = generations;
+        self
+    }
+
+    /// sets the number of independent runs whose best boards seed the final run, one means a single run
+    pub fn with_speciation_runs(mut self, runs: usize) -> Self {
+        self.speciation_runs = runs;
+        self
+    }
+
+    /// sets the number of generations after which a run stops, even if it is still improving
+    pub fn with_max_generations(mut self, generations: usize) -> Self {
+        self.max_generations = Some(generations);
+        self
+    }
+
+    /// sets whether the boards of a generation are scored on all cores, the threads share the dictionary.
+    /// the same seed gives the same board either way, on by default.
+    ///
+    /// `cargo run --release --example parallel_fitness` compares both on the machine at hand
+    pub fn with_parallel_fitness(mut self, parallel: bool) -> Self {
+        self.parallel_fitness = parallel;
+        self
+    }
+
+    /// gets the number of boards in every generation
+    pub fn population_size(&self) -> usize {
+        self.population_size
+    }
+
+    /// gets the mutation strategy
+    pub fn mutation(&self) -> GeneticMutation {
+        self.mutation
+    }
+
+    /// gets the crossover strategy
+    pub fn crossover(&self) -> GeneticCrossover {
+        self.crossover
+    }
+
+    /// gets the selection strategy
+    pub fn selection(&self) -> GeneticSelection {
+        self.selection
+    }
+
+    /// gets the extension shaking up a generation of boards too alike
+    pub fn extension(&self) -> GeneticExtension {
+        self.extension
+    }
+
+    /// gets the number of generations without a better board after which a run stops
+    pub fn max_stale_generations(&self) -> usize {
+        self.max_stale_generations
+    }
+
+    /// gets the number of independent runs
+    pub fn speciation_runs(&self) -> usize {
+        self.speciation_runs
+    }
+
+    /// gets the number of generations after which a run stops, if any
+    pub fn max_generations(&self) -> Option<usize> {
+        self.max_generations
+    }
+
+    /// whether the boards of a generation are scored on all cores
+    pub fn parallel_fitness(&self) -> bool {
+        self.parallel_fitness
+    }
+
+    /// checks every parameter is in its valid range
+    pub(crate) fn validate(&self) -> Result<(), BoggleError> {
+        let invalid = |reason: &str| Err(BoggleError::InvalidConfig(reason.to_string()));
+        let is_rate = |rate: f32| (0.0..=1.0).contains(&rate);
+
+        if self.population_size == 0 {
+            return invalid("the population size must be positive");
+        }
+        if self.max_stale_generations == 0 || self.max_generations == Some(0) {
+            return invalid("a run must last at least one generation");
+        }
+        match self.mutation {
+            GeneticMutation::SingleGene { probability } | GeneticMutation::MultiGene { probability, .. } if !is_rate(probability) => {
+                return invalid("the mutation probability must be between 0 and 1");
+            }
+            GeneticMutation::MultiGene { mutations: 0, .. } => return invalid("the number of mutations must be positive"),
+            _ => {}
+        }
+        match self.crossover {
+            GeneticCrossover::MultiGene { genes: 0 } | GeneticCrossover::MultiPoint { points: 0 } => {
+                return invalid("the number of crossovers must be positive");
+            }
+            _ => {}
+        }
+        match self.selection {
+            GeneticSelection::Elite { rate } | GeneticSelection::Tournament { rate, .. } if !is_rate(rate) => {
+                return invalid("the selection rate must be between 0 and 1");
+            }
+            GeneticSelection::Tournament { size: 0, .. } => return invalid("the tournament size must be positive"),
+            _ => {}
+        }
+        match self.extension {
+            GeneticExtension::MassDegeneration { cardinality: 0, .. } | GeneticExtension::MassDegeneration { mutations: 0, .. } => {
+                return invalid("the mass degeneration cardinality and number of mutations must be positive");
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn mutate_plugin(&self) -> MutateWrapper {
+        match self.mutation {
+            GeneticMutation::SingleGene { probability } => MutateSingleGene::new(probability).into(),
+            GeneticMutation::MultiGene { mutations, probability } => MutateMultiGene::new(mutations, probability).into(),
+        }
+    }
+
+    pub(crate) fn crossover_plugin(&self) -> CrossoverWrapper {
+        match self.crossover {
+            GeneticCrossover::Uniform => CrossoverUniform::new().into(),
+            GeneticCrossover::SingleGene => CrossoverSingleGene::new().into(),
+            GeneticCrossover::SinglePoint => CrossoverSinglePoint::new().into(),
+            GeneticCrossover::MultiGene { genes } => CrossoverMultiGene::new(genes, true).into(),
+            GeneticCrossover::MultiPoint { points } => CrossoverMultiPoint::new(points, true).into(),
+        }
+    }
+
+    pub(crate) fn select_plugin(&self) -> SelectWrapper {
+        match self.selection {
+            GeneticSelection::Elite { rate } => SelectElite::new(rate).into(),
+            GeneticSelection::Tournament { size, rate } => SelectTournament::new(size, rate).into(),
+        }
+    }
+
+    pub(crate) fn extension_plugin(&self) -> ExtensionWrapper {
+        match self.extension {
+            GeneticExtension::Noop => ExtensionWrapper::Noop(ExtensionNoop::new()),
+            GeneticExtension::MassDegeneration { cardinality, mutations } => {
+                ExtensionWrapper::MassDegeneration(ExtensionMassDegeneration::new(cardinality, mutations))
+            }
+        }
+    }
+}
+
+impl Default for GeneticConfig {
+    fn default() -> Self {
+        Self {
+            population_size: 100,
+            mutation: GeneticMutation::SingleGene { probability: 0.2 },
+            crossover: GeneticCrossover::Uniform,
+            selection: GeneticSelection::Elite { rate: 0.8 },
+            extension: GeneticExtension::MassDegeneration { cardinality: 2, mutations: 10 },
+            max_stale_generations: 25,
+            speciation_runs: 10,
+            max_generations: None,
+            parallel_fitness: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_valid(){
+        for config in [GeneticConfig::default(), GeneticConfig::fast(), GeneticConfig::thorough()] {
+            assert!(config.validate().is_ok());
+        }
+        assert!(GeneticConfig::fast().population_size() < GeneticConfig::thorough().population_size());
+    }
+
+    #[test]
+    fn rejects_out_of_range_parameters(){
+        let configs = [
+            GeneticConfig::default().with_population_size(0),
+            GeneticConfig::default().with_max_generations(0),
+            GeneticConfig::default().with_mutation(GeneticMutation::SingleGene { probability: 1.5 }),
+            GeneticConfig::default().with_crossover(GeneticCrossover::MultiPoint { points: 0 }),
+            GeneticConfig::default().with_selection(GeneticSelection::Tournament { size: 0, rate: 0.5 }),
+            GeneticConfig::default().with_extension(GeneticExtension::MassDegeneration { cardinality: 0, mutations: 10 }),
+            GeneticConfig::default().with_extension(GeneticExtension::MassDegeneration { cardinality: 2, mutations: 0 }),
+        ];
+        for config in configs {
+            assert!(matches!(config.validate(), Err(BoggleError::InvalidConfig(_))));
+        }
+    }
+}
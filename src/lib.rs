@@ -4,13 +4,34 @@
 
 pub mod builder;
 pub mod genetic_boggle_maker;
+pub mod genetic_config;
 pub mod simple_genetic_boggle_maker;
+pub mod local_search_boggle_maker;
 pub mod boggle_board;
 pub mod boggle_dfs;
 pub mod total_boggle_score_calculator;
 pub mod boggle_board_solver;
+pub mod dice;
+pub mod tile;
+pub mod scoring;
+pub mod error;
+pub mod generation;
+pub mod compiled_dictionary;
+pub mod boggle_dictionary;
+mod dictionary;
 
 pub use builder::BoggleBuilder;
 pub use boggle_board::Board;
+pub use dice::{Die,DiceSet};
+pub use tile::Tile;
+pub use error::BoggleError;
+pub use compiled_dictionary::CompiledDictionary;
+pub use boggle_dictionary::{BoggleDictionary,TrieNodeRef};
+pub use genetic_config::{GeneticConfig,GeneticMutation,GeneticCrossover,GeneticSelection,GeneticExtension};
+pub use genetic_boggle_maker::GeneticStrategy;
+pub use simple_genetic_boggle_maker::SimpleGeneticStrategy;
+pub use local_search_boggle_maker::{SimulatedAnnealingStrategy,HillClimbingStrategy};
+pub use generation::{TargetScore,GenerationStrategy,GenerationContext,GenerationReport,Progress,ProgressCallback,CancellationToken};
+pub use scoring::{ScoringRules,ClassicScoring,BigBoggleScoring,ScrabbleScoring,FlatScoring};
 pub use boggle_dfs::{WordVisitor,BoggleDfsContext,BoggleDfs};
-pub use boggle_board_solver::BoggleBoardSolver;
+pub use boggle_board_solver::{BoggleBoardSolver,BoggleBoardResult,BoggleWord,WordRejection};
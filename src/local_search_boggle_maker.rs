@@ -0,0 +1,390 @@
+use std::time::Instant;
+use rand::Rng;
+use crate::boggle_board::Board;
+use crate::tile::Tile;
+use crate::error::BoggleError;
+use crate::boggle_dictionary::BoggleDictionary;
+use crate::generation::{GenerationContext, GenerationReport, GenerationStrategy, BoardLayout, Progress};
+use crate::total_boggle_score_calculator::{TotalBoggleScoreCalculator, IncrementalScoreCalculator};
+
+/// The generation strategy optimising a single board by simulated annealing.
+///
+/// every iteration changes a random free cell to a random tile or swaps two free cells, a worse board is still
+/// kept with a probability shrinking with the temperature, which cools down from the initial to the final one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedAnnealingStrategy {
+    max_iterations: usize,
+    initial_temperature: f64,
+    final_temperature: f64,
+    swap_probability: f64,
+}
+
+impl SimulatedAnnealingStrategy {
+    /// initiate a new strategy running 5000 iterations, cooling down from 100 to 0.5 and swapping cells in 30% of the moves
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// sets the number of moves after which the best board is returned, even if it misses the target
+    pub fn with_max_iterations(mut self, iterations: usize) -> Self {
+        self.max_iterations = iterations;
+        self
+    }
+
+    /// sets the temperatures of the first and the last iteration, a board worse by the temperature's points is kept with a probability of 1/e
+    pub fn with_temperatures(mut self, initial: f64, last: f64) -> Self {
+        self.initial_temperature = initial;
+        self.final_temperature = last;
+        self
+    }
+
+    /// sets the probability of a move swapping two cells instead of changing one
+    pub fn with_swap_probability(mut self, probability: f64) -> Self {
+        self.swap_probability = probability;
+        self
+    }
+
+    /// gets the number of moves after which the best board is returned
+    pub fn max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+
+    /// gets the temperature of the first iteration
+    pub fn initial_temperature(&self) -> f64 {
+        self.initial_temperature
+    }
+
+    /// gets the temperature of the last iteration
+    pub fn final_temperature(&self) -> f64 {
+        self.final_temperature
+    }
+
+    /// gets the probability of a move swapping two cells
+    pub fn swap_probability(&self) -> f64 {
+        self.swap_probability
+    }
+
+    fn validate(&self) -> Result<(), BoggleError> {
+        let invalid = |reason: &str| Err(BoggleError::InvalidConfig(reason.to_string()));
+        if self.max_iterations == 0 {
+            return invalid("the annealing must last at least one iteration");
+        }
+        if !(self.final_temperature > 0.0 && self.final_temperature <= self.initial_temperature) {
+            return invalid("the temperatures must be positive and cool down");
+        }
+        if !(0.0..=1.0).contains(&self.swap_probability) {
+            return invalid("the swap probability must be between 0 and 1");
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for SimulatedAnnealingStrategy {
+    fn default() -> Self {
+        Self {
+            max_iterations: 5000,
+            initial_temperature: 100.0,
+            final_temperature: 0.5,
+            swap_probability: 0.3,
+        }
+    }
+}
+
+impl<D: BoggleDictionary> GenerationStrategy<D> for SimulatedAnnealingStrategy {
+    fn generate(&self, context: &GenerationContext<D>) -> Result<Option<GenerationReport>, BoggleError> {
+        context.validate()?;
+        self.validate()?;
+
+        let started = Instant::now();
+        let mut rng = context.rng();
+        let Some(search) = LocalSearch::new(context, &mut rng) else {
+            return Ok(None);
+        };
+
+        let mut current = search.start(&mut rng);
+        let mut fitness = search.fitness(&current);
+        let mut best = search.candidate(&current, fitness);
+        let cooling = (self.final_temperature / self.initial_temperature).ln();
+        for iteration in 0..self.max_iterations {
+            if best.fitness == 0 || context.should_stop(started) {
+                break;
+            }
+
+            let temperature = self.initial_temperature * (cooling * iteration as f64 / self.max_iterations as f64).exp();
+            search.random_move(self.swap_probability, &mut rng).apply(&mut current);
+            let next = search.fitness(&current);
+
+            let delta = (next - fitness) as f64;
+            if delta >= 0.0 || rng.random::<f64>() < (delta / temperature).exp() {
+                fitness = next;
+                if fitness > best.fitness {
+                    best = search.candidate(&current, fitness);
+                }
+            } else {
+                current.undo();
+            }
+            context.report(&Progress::new(0, iteration + 1, Some(best.score as isize), started.elapsed()));
+        }
+
+        Ok(best.into_report(context))
+    }
+}
+
+/// The generation strategy optimising a board by steepest-ascent hill climbing.
+///
+/// every step tries each change of a free cell to another tile and each swap of two free cells, and keeps the best
+/// board among them. a climb ends on a board none of them improves, and the next climb restarts from a random board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HillClimbingStrategy {
+    climbs: usize,
+    max_steps: usize,
+}
+
+impl HillClimbingStrategy {
+    /// initiate a new strategy of up to 10 climbs of up to 100 steps
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// sets the number of climbs from a random board, the best board of all of them is returned
+    pub fn with_climbs(mut self, climbs: usize) -> Self {
+        self.climbs = climbs;
+        self
+    }
+
+    /// sets the number of steps after which a climb ends, even if it is still improving
+    pub fn with_max_steps(mut self, steps: usize) -> Self {
+        self.max_steps = steps;
+        self
+    }
+
+    /// gets the number of climbs
+    pub fn climbs(&self) -> usize {
+        self.climbs
+    }
+
+    /// gets the number of steps after which a climb ends
+    pub fn max_steps(&self) -> usize {
+        self.max_steps
+    }
+}
+
+impl Default for HillClimbingStrategy {
+    fn default() -> Self {
+        Self { climbs: 10, max_steps: 100 }
+    }
+}
+
+impl<D: BoggleDictionary> GenerationStrategy<D> for HillClimbingStrategy {
+    fn generate(&self, context: &GenerationContext<D>) -> Result<Option<GenerationReport>, BoggleError> {
+        context.validate()?;
+        if self.climbs == 0 || self.max_steps == 0 {
+            return Err(BoggleError::InvalidConfig("there must be at least one climb of one step".to_string()));
+        }
+
+        let started = Instant::now();
+        let mut rng = context.rng();
+        let Some(search) = LocalSearch::new(context, &mut rng) else {
+            return Ok(None);
+        };
+
+        let mut best: Option<Candidate> = None;
+        'climbs: for climb in 0..self.climbs {
+            let mut current = search.start(&mut rng);
+            let mut fitness = search.fitness(&current);
+            for step in 0..self.max_steps {
+                if fitness == 0 || context.should_stop(started) {
+                    break;
+                }
+
+                //every neighbour is scored by changing the board and changing it back
+                let mut steepest: Option<(Move, isize)> = None;
+                for change in search.neighbours(current.board()) {
+                    change.apply(&mut current);
+                    let next = search.fitness(&current);
+                    current.undo();
+                    if steepest.is_none_or(|(_, steepest)| next > steepest) {
+                        steepest = Some((change, next));
+                    }
+                }
+
+                //a local optimum ends the climb
+                match steepest {
+                    Some((change, next)) if next > fitness => {
+                        change.apply(&mut current);
+                        fitness = next;
+                    }
+                    _ => break,
+                }
+                context.report(&Progress::new(climb, step + 1, Some(current.score() as isize), started.elapsed()));
+            }
+
+            let current = search.candidate(&current, fitness);
+            if best.as_ref().is_none_or(|best| current.fitness > best.fitness) {
+                best = Some(current);
+            }
+            let best = best.as_ref().expect("the climb's board is kept");
+            if best.fitness == 0 || context.should_stop(started) {
+                break 'climbs;
+            }
+        }
+
+        Ok(best.and_then(|best| best.into_report(context)))
+    }
+}
+
+/// A change of the free cells of a board.
+#[derive(Clone, Copy, Debug)]
+enum Move {
+    /// puts the tile on the cell of the index
+    Set(usize, Tile),
+    /// swaps the tiles of the cells of the indexes
+    Swap(usize, usize),
+}
+
+impl Move {
+    /// changes the board, only rescoring the words through the changed cells
+    fn apply<D: BoggleDictionary>(self, board: &mut IncrementalScoreCalculator<D>) {
+        match self {
+            Move::Set(index, tile) => board.set(index, tile),
+            Move::Swap(a, b) => board.swap(a, b),
+        };
+    }
+}
+
+/// A board of a search with its score and fitness.
+#[derive(Clone, Debug)]
+struct Candidate {
+    board: Vec<Tile>,
+    score: u32,
+    fitness: isize,
+}
+
+impl Candidate {
+    /// the report of the board, `None` if it misses a required word or has a blocklisted word
+    fn into_report<D: BoggleDictionary>(self, context: &GenerationContext<D>) -> Option<GenerationReport> {
+        if !context.accepts(&self.board) {
+            return None;
+        }
+        let board = Board::new(self.board, context.width(), context.length(), Some(self.score as isize));
+
+        Some(GenerationReport::new(board, context.target()))
+    }
+}
+
+/// The moves and scoring shared by the local searches, a move only rescores the words through the cells it changes.
+struct LocalSearch<'c, 'a, D: BoggleDictionary> {
+    context: &'c GenerationContext<'a, D>,
+    calculator: TotalBoggleScoreCalculator<'a, D>,
+    layout: BoardLayout,
+    free: Vec<usize>,
+    tiles: Vec<Tile>,
+}
+
+impl<'c, 'a, D: BoggleDictionary> LocalSearch<'c, 'a, D> {
+    /// a search of the boards of the context, `None` if every cell is locked or blocked
+    fn new<R: Rng + ?Sized>(context: &'c GenerationContext<'a, D>, rng: &mut R) -> Option<Self> {
+        let layout = BoardLayout::new(context, rng);
+        let free = layout.free_indexes();
+        if free.is_empty() {
+            return None;
+        }
+
+        //a tile repeated in the alphabet is still a single move
+        let mut tiles: Vec<Tile> = Vec::new();
+        for &tile in context.alphabet() {
+            if !tiles.contains(&tile) {
+                tiles.push(tile);
+            }
+        }
+        let calculator = TotalBoggleScoreCalculator::new(context.dictionary(), context.width(), context.length())
+            .with_scoring(context.scoring().clone());
+
+        Some(Self { context, calculator, layout, free, tiles })
+    }
+
+    /// a solved random board
+    fn start<R: Rng + ?Sized>(&self, rng: &mut R) -> IncrementalScoreCalculator<'a, D> {
+        let board = self.layout.random_board(self.context.alphabet(), rng);
+        self.calculator.incremental(&board).expect("the board fits the context")
+    }
+
+    fn fitness(&self, board: &IncrementalScoreCalculator<D>) -> isize {
+        self.context.fitness(board.score(), board.board())
+    }
+
+    fn candidate(&self, board: &IncrementalScoreCalculator<D>, fitness: isize) -> Candidate {
+        Candidate { board: board.board().to_vec(), score: board.score(), fitness }
+    }
+
+    /// a random change of a free cell to a tile of the alphabet, or a random swap of two free cells
+    fn random_move<R: Rng + ?Sized>(&self, swap_probability: f64, rng: &mut R) -> Move {
+        if self.free.len() > 1 && rng.random::<f64>() < swap_probability {
+            let a = rng.random_range(0..self.free.len());
+            let b = (a + rng.random_range(1..self.free.len())) % self.free.len();
+            return Move::Swap(self.free[a], self.free[b]);
+        }
+
+        let index = self.free[rng.random_range(0..self.free.len())];
+        let alphabet = self.context.alphabet();
+        Move::Set(index, alphabet[rng.random_range(0..alphabet.len())])
+    }
+
+    /// every change of a free cell to another tile and every swap of two free cells of different tiles
+    fn neighbours(&self, board: &[Tile]) -> Vec<Move> {
+        let mut moves = Vec::new();
+        for (position, &index) in self.free.iter().enumerate() {
+            moves.extend(self.tiles.iter().filter(|&&tile| tile != board[index]).map(|&tile| Move::Set(index, tile)));
+            moves.extend(self.free[position + 1..].iter().filter(|&&other| board[other] != board[index]).map(|&other| Move::Swap(index, other)));
+        }
+
+        moves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::load_dictionary;
+    use crate::generation::TargetScore;
+
+    #[test]
+    fn annealing_reaches_a_target_window(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let context = GenerationContext::new(&dictionary, 4, 4)
+            .with_target(TargetScore::between(300, 400))
+            .with_locked_cell(1, 1, Tile::from('E'))
+            .with_seed(5);
+
+        let strategy = SimulatedAnnealingStrategy::new();
+        let report = strategy.generate(&context).unwrap().unwrap();
+        assert!(report.target_hit(), "score {} is out of the window", report.score());
+        assert_eq!(report.board().get(1, 1), Some(Tile::from('E')));
+        assert_eq!(report.board().hash(), strategy.generate(&context).unwrap().unwrap().board().hash());
+    }
+
+    #[test]
+    fn hill_climbing_reaches_a_target_window(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let context = GenerationContext::new(&dictionary, 4, 4)
+            .with_target(TargetScore::between(150, 200))
+            .with_blocked_cells(2)
+            .with_seed(5);
+
+        let report = HillClimbingStrategy::new().generate(&context).unwrap().unwrap();
+        assert!(report.target_hit(), "score {} is out of the window", report.score());
+        assert_eq!(report.board().value().iter().filter(|tile| tile.is_blocked()).count(), 2);
+    }
+
+    #[test]
+    fn rejects_out_of_range_parameters(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let context = GenerationContext::new(&dictionary, 4, 4);
+
+        let annealing = SimulatedAnnealingStrategy::new().with_temperatures(1.0, 10.0).generate(&context);
+        assert!(matches!(annealing, Err(BoggleError::InvalidConfig(_))));
+        let climbing = HillClimbingStrategy::new().with_climbs(0).generate(&context);
+        assert!(matches!(climbing, Err(BoggleError::InvalidConfig(_))));
+    }
+}
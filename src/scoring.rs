@@ -0,0 +1,122 @@
+use std::fmt::Debug;
+
+/// The scoring rules trait, deciding how many points a word found on the board is worth.
+pub trait ScoringRules: Debug + Send + Sync {
+    /// gets the score of a word, zero for words which do not count
+    fn word_score(&self, word: &str) -> u32;
+
+    /// gets the minimum number of letters a word needs to count
+    fn min_word_length(&self) -> usize {
+        3
+    }
+}
+
+/// The classic boggle scoring: 1 point for 3 and 4 letters, 2 for 5, 3 for 6, 5 for 7 and 11 for 8 or more.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClassicScoring;
+
+impl ScoringRules for ClassicScoring {
+    fn word_score(&self, word: &str) -> u32 {
+        let len = word.chars().count();
+        match len {
+            3 => 1,
+            4 => 1,
+            5 => 2,
+            6 => 3,
+            7 => 5,
+            _ if len >= 8 => 11,
+            _ => 0,
+        }
+    }
+}
+
+/// The big boggle scoring, same as the classic scoring but words need at least 4 letters.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BigBoggleScoring;
+
+impl ScoringRules for BigBoggleScoring {
+    fn word_score(&self, word: &str) -> u32 {
+        if word.chars().count() < self.min_word_length() {
+            return 0;
+        }
+        ClassicScoring.word_score(word)
+    }
+
+    fn min_word_length(&self) -> usize {
+        4
+    }
+}
+
+/// The scrabble style scoring, a word is worth the sum of its letters' scrabble values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScrabbleScoring;
+
+impl ScrabbleScoring {
+    /// gets the scrabble value of a letter, zero for non english letters
+    pub fn letter_value(ch: char) -> u32 {
+        match ch.to_ascii_lowercase() {
+            'a' | 'e' | 'i' | 'l' | 'n' | 'o' | 'r' | 's' | 't' | 'u' => 1,
+            'd' | 'g' => 2,
+            'b' | 'c' | 'm' | 'p' => 3,
+            'f' | 'h' | 'v' | 'w' | 'y' => 4,
+            'k' => 5,
+            'j' | 'x' => 8,
+            'q' | 'z' => 10,
+            _ => 0,
+        }
+    }
+}
+
+impl ScoringRules for ScrabbleScoring {
+    fn word_score(&self, word: &str) -> u32 {
+        if word.chars().count() < self.min_word_length() {
+            return 0;
+        }
+        word.chars().map(Self::letter_value).sum()
+    }
+}
+
+/// The flat scoring, every word is worth one point.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlatScoring;
+
+impl ScoringRules for FlatScoring {
+    fn word_score(&self, word: &str) -> u32 {
+        if word.chars().count() < self.min_word_length() { 0 } else { 1 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_scoring_follows_the_boggle_table(){
+        let scores: Vec<u32> = ["AT", "CAT", "CATS", "CRATE", "CRATES", "SCATTER", "SCATTERS"]
+            .iter()
+            .map(|word| ClassicScoring.word_score(word))
+            .collect();
+        assert_eq!(scores, vec![0, 1, 1, 2, 3, 5, 11]);
+    }
+
+    #[test]
+    fn big_boggle_scoring_needs_four_letters(){
+        assert_eq!(BigBoggleScoring.word_score("CAT"), 0);
+        assert_eq!(BigBoggleScoring.word_score("CATS"), 1);
+        assert_eq!(BigBoggleScoring.min_word_length(), 4);
+    }
+
+    #[test]
+    fn scrabble_scoring_sums_letter_values(){
+        assert_eq!(ScrabbleScoring.word_score("QUIZ"), 22);
+        assert_eq!(ScrabbleScoring.word_score("cat"), 5);
+        assert_eq!(ScrabbleScoring.word_score("AX"), 0);
+    }
+
+    #[test]
+    fn flat_scoring_gives_one_point_per_word(){
+        assert_eq!(FlatScoring.word_score("AT"), 0);
+        assert_eq!(FlatScoring.word_score("CAT"), 1);
+        assert_eq!(FlatScoring.word_score("SCATTERS"), 1);
+    }
+}
@@ -1,153 +1,182 @@
 use crate::boggle_board::Board;
-use word_trie::trie::{Trie,TrieNode};
+use crate::tile::Tile;
+use crate::scoring::ScoringRules;
+use crate::error::BoggleError;
+use crate::boggle_dfs::BoggleDfsContext;
+use crate::boggle_dictionary::BoggleDictionary;
+use crate::generation::{GenerationContext, GenerationReport, GenerationStrategy, BoardLayout, Progress, TargetScore};
+use crate::compiled_dictionary::CompiledDictionary;
+use crate::total_boggle_score_calculator::total_score;
 use rand::Rng;
 use rand::distr::{Distribution, Uniform};
-use std::collections::{HashSet,HashMap};
+use std::collections::HashSet;
+use std::time::Instant;
 
-pub struct GeneticBoard{
-    board:Board,
-    age:u32,
-    score:usize,
+/// the default generation after which the best board is returned, even if it misses the target
+const MAX_GENERATIONS: usize = 20;
+
+/// the default number of boards kept in every generation
+const POPULATION_SIZE: usize = 10;
+
+/// The generation strategy evolving the boards with the crate's own simple genetic algorithm.
+///
+/// every generation mates the best boards of the previous one by tournaments of two, and keeps the best of their children.
+/// a child takes a fifth to a third of its cells from the other board or at random, too many to be scored incrementally,
+/// so every child is scored with a full search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimpleGeneticStrategy {
+    population_size: usize,
+    max_generations: usize,
 }
 
-impl GeneticBoard{
-    pub fn get_board(dictionary:&Trie, minimum_score:usize, board_x:usize,board_y:usize)->Board{
-        const POPULATION_SIZE:usize = 10;
-        let mut choromosomes : Vec<GeneticBoard> = Self::init_population(dictionary,POPULATION_SIZE, board_x, board_y);
+impl SimpleGeneticStrategy {
+    /// initiate a new strategy keeping 10 boards in every generation, for up to 20 generations
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        let mut generation = 0;
-        loop{
-            
-            //stop mating if the minimum requiremet is met.
-            if let Some(x) = choromosomes.first() {
-                println!("hello board, genetic generated : {} : {} : {}", x.board.hash(),x.age,x.score);
-                if x.score > minimum_score || generation>20 {
-                    return x.board.copy();
-                }
-            }
+    /// sets the number of boards kept in every generation, at least two are needed to mate
+    pub fn with_population_size(mut self, size: usize) -> Self {
+        self.population_size = size;
+        self
+    }
 
-            choromosomes = Self::evolve_population(dictionary,POPULATION_SIZE,&choromosomes);
-            generation+=1;
-        }
+    /// sets the number of generations after which the best board is returned, even if it misses the target
+    pub fn with_max_generations(mut self, generations: usize) -> Self {
+        self.max_generations = generations;
+        self
+    }
 
+    /// gets the number of boards kept in every generation
+    pub fn population_size(&self) -> usize {
+        self.population_size
     }
 
-    // fn tournament_select(choromosomes : &Vec<GeneticBoard>, uniform: &Uniform<u32>, rng: &mut impl Rng)->usize{
-    //     let mut a = uniform.sample(&mut rng);
-    //     for i in 0..4 {
-    //         let b = uniform.sample(&mut rng);
-    //         if choromosomes[b].score > choromosomes[a].score {
-    //             a = b;
-    //         }
-    //     }
-    //     return a;
-    // }
-    fn evolve_population(dictionary:&Trie,size:usize, prev_generation : &Vec<GeneticBoard>) -> Vec<GeneticBoard>{
-        let mut choromosomes : Vec<GeneticBoard> = Vec::new();
-        let new_borns : HashSet<String> = HashSet::new();
-        let uniform = Uniform::try_from(0..size).unwrap();
-        let mut rng = rand::rng();
-        while choromosomes.len() < size*10 {            
-            let mut a;
-            {
-                a = uniform.sample(&mut rng);
-                for _i in 0..1 {
-                    let c = uniform.sample(&mut rng);
-                    if prev_generation[c].score > prev_generation[a].score {
-                        a = c;
-                    }
-                }
-            }
-            let mut b = a;
-            while b==a {
-                b = uniform.sample(&mut rng);
-                for _i in 0..1 {
-                    let c = uniform.sample(&mut rng);
-                    if prev_generation[c].score > prev_generation[b].score {
-                        b = c;
-                    }
-                }
-            }
+    /// gets the number of generations after which the best board is returned
+    pub fn max_generations(&self) -> usize {
+        self.max_generations
+    }
+}
 
-            //let b = Self::tournament_select(prev_generation, &between,&mut rng);
-            let mut born = prev_generation[a].merge(&prev_generation[b]);
-            let key = born.board.hash();
-            if new_borns.contains(&key) {
-                continue;
-            }
-            born.score = Self::get_board_score(dictionary,&born.board);
-            choromosomes.push(born);
+impl Default for SimpleGeneticStrategy {
+    fn default() -> Self {
+        Self {
+            population_size: POPULATION_SIZE,
+            max_generations: MAX_GENERATIONS,
         }
-        for i in 0..1 {
-            if let Some(x) = prev_generation.get(i) {
-                let key = x.board.hash();
-                if !new_borns.contains(&key) {                
-                    choromosomes.push(Self{
-                        board:x.board.copy(),
-                        age:x.age,
-                        score:x.score
-                    });
-                }
-            }
+    }
+}
+
+impl<D: BoggleDictionary> GenerationStrategy<D> for SimpleGeneticStrategy {
+    fn generate(&self, context: &GenerationContext<D>) -> Result<Option<GenerationReport>, BoggleError> {
+        context.validate()?;
+        if self.population_size < 2 {
+            return Err(BoggleError::InvalidConfig("the population size must be at least two".to_string()));
         }
 
-        choromosomes.sort_by(|a,b| b.score.cmp(&a.score));
-        choromosomes.truncate(size);
+        let started = Instant::now();
+        let mut rng = context.rng();
+        let layout = BoardLayout::new(context, &mut rng);
+        if layout.free_cells() == 0 {
+            return Ok(None);
+        }
 
-        choromosomes
-    }
-    fn init_population(dictionary:&Trie,size:usize, board_x:usize,board_y:usize) -> Vec<GeneticBoard>{
-        let mut choromosomes : Vec<GeneticBoard> = Vec::new();
-        let mut new_borns = HashSet::new();
-        while new_borns.len()<size {
-            let brd = Board::new_random(board_x,board_y);
-            let key = brd.hash();
-            if new_borns.contains(&key) {
-                continue;
-            }
-            let score = Self::get_board_score(dictionary,&brd);
+        let fitness = |score: u32, board: &Board| context.fitness(score, board.value());
+        let evolution = Evolution {
+            dfs: BoggleDfsContext::new(context.dictionary(), context.width(), context.length()),
+            alphabet: context.alphabet(),
+            scoring: context.scoring().as_ref(),
+            layout,
+            fitness: &fitness,
+            population_size: self.population_size,
+        };
+        let best = evolution.run(&mut rng, &mut |generation, best| {
+            context.report(&Progress::new(0, generation, Some(best.score as isize), started.elapsed()));
+            best.fitness == 0 || generation >= self.max_generations || context.should_stop(started)
+        });
 
-            let gen_board = Self{
-                board:brd,
-                age:0,
-                score:score
-            };
-            new_borns.insert(key);
-            choromosomes.push(gen_board);
+        if !context.accepts(best.board.value()) {
+            return Ok(None);
         }
-        choromosomes.sort_by(|a,b| b.score.cmp(&a.score));
+        let board = Board::new(best.board.value().to_vec(), context.width(), context.length(), Some(best.score as isize));
 
-        choromosomes
+        Ok(Some(GenerationReport::new(board, context.target())))
     }
+}
+
+/// The settings of a run of the simple genetic algorithm.
+struct Evolution<'r, D: BoggleDictionary> {
+    dfs: BoggleDfsContext<'r, D>,
+    alphabet: &'r [Tile],
+    scoring: &'r dyn ScoringRules,
+    layout: BoardLayout,
+    fitness: &'r dyn Fn(u32, &Board) -> isize,
+    population_size: usize,
+}
+
+/// A board of a generation with its score and fitness.
+pub struct GeneticBoard{
+    board:Board,
+    age:u32,
+    score:u32,
+    fitness:isize,
+}
 
-    fn merge(&self, other : &GeneticBoard) -> Self {
-        let boards = Self::order(&self,other);
+impl GeneticBoard{
+    /// generates a board scoring more than `minimum_score`, or the best board found by the last generation
+    #[deprecated(note = "use `SimpleGeneticStrategy` with a `GenerationContext`")]
+    pub fn get_board(dictionary:&CompiledDictionary, alphabet:&[Tile], scoring:&dyn ScoringRules, minimum_score:usize, board_x:usize,board_y:usize)->Result<Board, BoggleError>{
+        #[allow(deprecated)]
+        Self::get_board_with(dictionary, alphabet, scoring, minimum_score, board_x, board_y, &mut rand::rng())
+    }
 
-        let mut merged = Self{
-            board:boards.0.board.copy(),
-            age:boards.0.age+1,
-            score:0
+    /// same as `get_board` using the given random number generator, the same seeded generator always gives the same board
+    #[deprecated(note = "use `SimpleGeneticStrategy` with a `GenerationContext` and a seed")]
+    pub fn get_board_with<R: Rng + ?Sized>(dictionary:&CompiledDictionary, alphabet:&[Tile], scoring:&dyn ScoringRules, minimum_score:usize, board_x:usize,board_y:usize, rng:&mut R)->Result<Board, BoggleError>{
+        BoggleError::check_board(board_x, board_y, board_x.saturating_mul(board_y))?;
+        if alphabet.is_empty() {
+            return Err(BoggleError::InvalidTile("the alphabet is empty".to_string()));
+        }
+
+        //the same population and generations as the default strategy
+        let strategy = SimpleGeneticStrategy::default();
+        let target = TargetScore::at_least(minimum_score.saturating_add(1) as isize);
+        let fitness = |score: u32, _: &Board| -target.distance(score as isize);
+        let evolution = Evolution {
+            dfs: BoggleDfsContext::new(dictionary, board_x, board_y),
+            alphabet,
+            scoring,
+            layout: BoardLayout::free(board_x * board_y),
+            fitness: &fitness,
+            population_size: strategy.population_size(),
         };
+        let best = evolution.run(rng, &mut |generation, best| best.fitness == 0 || generation >= strategy.max_generations());
+
+        Ok(best.board)
+    }
+
+    fn merge<R: Rng + ?Sized>(&self, other : &GeneticBoard, alphabet:&[Tile], rng:&mut R) -> Board {
+        let boards = Self::order(self,other,rng);
+
+        let mut merged = boards.0.board.copy();
 
         let similarity = Self::similarity(&boards.0.board,&boards.1.board);
 
-        let first_share = if similarity < 0.80 { 70 } else { 70 };
+        let first_share = 70;
         let second_share = if similarity < 0.80 { 99 } else { 80 };
 
         let uniform = Uniform::try_from(0..101).unwrap();
-        let uniform2 = Uniform::try_from(0..26).unwrap();
-        let mut rng = rand::rng();
+        let uniform2 = Uniform::try_from(0..alphabet.len()).unwrap();
 
-        for i in 0..boards.0.board.width() {
-            for j in 0..boards.0.board.length() {
-                let rand = uniform.sample(&mut rng);
+        for i in 0..boards.0.board.length() {
+            for j in 0..boards.0.board.width() {
+                let rand = uniform.sample(rng);
                 if rand < first_share {continue;}//the board cell can stay the initilized value.
-                else if rand < second_share {merged.board.set(i,j,boards.1.board.get(i,j).expect("must have value always"));} // get chromosoms from the second board
+                else if rand < second_share {merged.set(i,j,boards.1.board.get(i,j).expect("must have value always"));} // get chromosoms from the second board
                 else {
                     //mutate
-                    let rnd = uniform2.sample(&mut rng);
-                    let random_char = (b'A' + rnd) as char;
-                    merged.board.set(i,j,random_char);
+                    let rnd = uniform2.sample(rng);
+                    merged.set(i,j,alphabet[rnd]);
                 }
             }
         }        
@@ -157,10 +186,9 @@ impl GeneticBoard{
 
     //this function's return type contains a borrowed value, but the signature does not say whether it is borrowed from `this` or `that`
     //help: consider introducing a named lifetime parameter
-    fn order<'a>(this: &'a GeneticBoard,that: &'a GeneticBoard)->(&'a GeneticBoard,&'a GeneticBoard){
-        if this.score>that.score { return (this,that); }
-        if this.score<that.score { return (that,this); }
-        let mut rng = rand::rng();
+    fn order<'a, R: Rng + ?Sized>(this: &'a GeneticBoard,that: &'a GeneticBoard, rng:&mut R)->(&'a GeneticBoard,&'a GeneticBoard){
+        if this.fitness>that.fitness { return (this,that); }
+        if this.fitness<that.fitness { return (that,this); }
         let rnd = rng.random_range(0..=1);
         if rnd==0 { return (this,that);}
         (that,this)
@@ -168,8 +196,8 @@ impl GeneticBoard{
 
     fn similarity(this:&Board, that:&Board) -> f32{
         let mut same:f32 = 0.0;
-        for i in 0..this.width() {
-            for j in 0..this.length() {
+        for i in 0..this.length() {
+            for j in 0..this.width() {
                 let ch1 = this.get(i,j).expect("must have value always");
                 let ch2 = that.get(i,j).expect("must have value always");
                 if ch1==ch2 { same+=1.0;}
@@ -177,115 +205,169 @@ impl GeneticBoard{
         }
         same/(this.width() as f32 * this.length() as f32)
     }
+}
+
+impl<D: BoggleDictionary> Evolution<'_, D> {
+    /// evolves the boards until `is_done`, called with the generation and the best board, returns true, and gets the best board
+    fn run<R: Rng + ?Sized>(&self, rng:&mut R, is_done:&mut dyn FnMut(usize, &GeneticBoard) -> bool) -> GeneticBoard {
+        let mut choromosomes = self.init_population(rng);
 
-    /// gets the score of a boggle board.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use word_trie::TrieBuilder;
-    /// use boggle_maker::Board;
-    /// let trie = TrieBuilder::new()
-    /// .from_file("words.txt")
-    /// .expect("Failed to load trie from file");
-    ///
-    /// //SERSPATGLINESERS
-    /// let sample = Board::new(
-    ///         vec!['S','E','R','S','P','A','T','G','L','I','N','E','S','E','R','S']
-    ///         ,4,4, None);
-    /// //SOTGPRNSEAIESTTL
-    /// let sample2 = Board::new(
-    ///     vec!['S','O','T','G','P','R','N','S','E','A','I','E','S','T','T','L']
-    ///     ,4,4,None);
-    /// // let score = get_board_score(&trie,&sample);
-    /// //assert!(score>3999);
-    /// //let score = get_board_score(&trie,&sample2);
-    /// //assert!(score>3999);
-    /// ```
-    fn get_board_score(dictionary:&Trie,brd:&Board) -> usize{         
-        let mut set = HashMap::new();
-        let mut visited = HashMap::new(); 
-
-        let mut current = String::new();
-        for i in 0..brd.width(){
-            for j in 0..brd.length(){
-                Self::get_board_score_from(&dictionary.root,brd,&mut set,&mut visited,i,j,&mut current);
+        let mut generation = 0;
+        loop{
+            //stop mating if the requirements are met, or there are not two boards to mate.
+            if choromosomes.len() < 2 || is_done(generation, &choromosomes[0]) {
+                return choromosomes.swap_remove(0);
             }
-        }
 
-        let mut score = 0;
+            choromosomes = self.evolve_population(&choromosomes, rng);
+            generation+=1;
+        }
+    }
 
-        for (_, val) in &set {
-            score += val;
+    /// scores a board, keeping the layout's locked and blocked cells on it
+    fn born(&self, mut board:Board, age:u32) -> GeneticBoard {
+        let width = board.width();
+        for (index, tile) in self.layout.fixed_cells() {
+            board.set(index / width, index % width, tile);
         }
+        let score = total_score(&self.dfs, board.value(), self.scoring);
+        let fitness = (self.fitness)(score, &board);
 
-        score
+        GeneticBoard{ board, age, score, fitness }
     }
 
-    fn get_board_score_from(mut node:&TrieNode ,
-            brd:&Board,
-            set:&mut HashMap<String,usize>,
-            visited:&mut HashMap<usize,bool>,
-            x:usize,
-            y:usize,
-            current:&mut String
-        ){
-        
-        let cell_index = x*brd.width() + y;
-        let is_visited = visited.entry(cell_index).or_insert(false);
-        //if the board's current cell is visited then return.
-        if *is_visited {
-            return;
-        }
-        
-        //mark the current board's cell as visited
-        *is_visited = true;
-        let ch = brd.get(x,y).expect("the board must has value in all cells");
-        
-        //check if the trie has this node
-        let ch_index = ch.to_ascii_lowercase();        
-        match node.nodes.get(&ch_index){
-            Some(next_node) => node = next_node,
-            None => {
-                //if the trie current node does not have the board's current cell's letter then revert the status and return 
-                visited.insert(cell_index,false);
-                return;
-            },
+    fn init_population<R: Rng + ?Sized>(&self, rng:&mut R) -> Vec<GeneticBoard>{
+        let mut choromosomes : Vec<GeneticBoard> = Vec::new();
+        let mut new_borns = HashSet::new();
+        //a board with few free cells may not have `size` distinct layouts
+        let mut attempts = 0;
+        while new_borns.len()<self.population_size && attempts < self.population_size * 100 {
+            attempts += 1;
+            let board = Board::new_random_from_with(self.dfs.width(),self.dfs.length(),self.alphabet,rng)
+                .expect("the context's alphabet is not empty");
+            let brd = self.born(board, 0);
+            if new_borns.insert(brd.board.hash()) {
+                choromosomes.push(brd);
+            }
         }
-        //add current cell's letter to the current word
-        current.push(ch);
-
-        //check if the current word is a valid word in the dictionary 
-        //if yes then check if it's not been added to the result set yet
-        //if not added then add it to the result set with a proper calculated score
-        if node.is_word && !set.contains_key(current) {                
-            set.insert(current.to_string(),Self::get_score(current.len()));
+        choromosomes.sort_by_key(|x| std::cmp::Reverse(x.fitness));
+
+        choromosomes
+    }
+
+    fn evolve_population<R: Rng + ?Sized>(&self, prev_generation : &[GeneticBoard], rng:&mut R) -> Vec<GeneticBoard>{
+        let size = self.population_size;
+        let mut choromosomes : Vec<GeneticBoard> = Vec::new();
+        let mut new_borns : HashSet<String> = HashSet::new();
+        let uniform = Uniform::try_from(0..prev_generation.len()).unwrap();
+        //a board with few free cells may not have `size*10` distinct children
+        let mut attempts = 0;
+        while choromosomes.len() < size*10 && attempts < size * 100 {
+            attempts += 1;
+            let tournament = |rng:&mut R| {
+                let a = uniform.sample(rng);
+                let c = uniform.sample(rng);
+                if prev_generation[c].fitness > prev_generation[a].fitness { c } else { a }
+            };
+            let a = tournament(rng);
+            let mut b = a;
+            while b==a {
+                b = tournament(rng);
+            }
+
+            let parent = &prev_generation[a];
+            let born = self.born(parent.merge(&prev_generation[b],self.alphabet,rng), parent.age.max(prev_generation[b].age)+1);
+            if new_borns.insert(born.board.hash()) {
+                choromosomes.push(born);
+            }
         }
 
-        //Recursively check all neighbour cells 
-        for (a,b) in [(-1,-1),(-1,0),(-1,1),(0,-1),(0,1),(1,-1),(1,0),(1,1)]{
-            let next_x = x as i8 + a;
-            let next_y = y as i8 + b;
-            if (0..brd.width() as i8).contains(&next_x) && (0..brd.length() as i8).contains(&next_y){
-                Self::get_board_score_from(node,brd, set, visited,next_x as usize,next_y as usize, current); 
-            }           
+        //the best board of the previous generation survives, unless one of its children is the same board
+        if let Some(x) = prev_generation.first()
+            && new_borns.insert(x.board.hash()) {
+            choromosomes.push(GeneticBoard{
+                board:x.board.copy(),
+                age:x.age,
+                score:x.score,
+                fitness:x.fitness,
+            });
         }
 
-        //Remove current visited cell letter from current word end.
-        current.pop();
+        choromosomes.sort_by_key(|x| std::cmp::Reverse(x.fitness));
+        choromosomes.truncate(size);
 
-        //mark the current cell as not visited 
-        visited.insert(cell_index,false);        
+        choromosomes
     }
+}
 
-    fn get_score(len:usize)->usize{
-        match len{
-            0..=2 => 0,
-            3..=4 => 1,
-            5 => 2,
-            6 => 3,
-            7 => 5,
-            _ => 11
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::dictionary::load_dictionary;
+    use crate::scoring::ClassicScoring;
+    use crate::tile::default_alphabet;
+
+    #[test]
+    fn generated_board_keeps_the_locked_cells(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let context = GenerationContext::new(&dictionary, 4, 4)
+            .with_target(TargetScore::at_least(100))
+            .with_locked_cell(0, 0, Tile::from('Q'))
+            .with_locked_cell(3, 3, Tile::BLOCKED)
+            .with_seed(11);
+
+        let strategy = SimpleGeneticStrategy::new();
+        let report = strategy.generate(&context).unwrap().unwrap();
+        assert_eq!(report.board().get(0, 0), Some(Tile::from('Q')));
+        assert_eq!(report.board().get(3, 3), Some(Tile::BLOCKED));
+        assert_eq!(report.board().hash(), strategy.generate(&context).unwrap().unwrap().board().hash());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn reaches_a_target_window(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let context = GenerationContext::new(&dictionary, 4, 4)
+            .with_target(TargetScore::between(150, 250))
+            .with_seed(3);
+
+        let report = SimpleGeneticStrategy::new().with_max_generations(200).generate(&context).unwrap().unwrap();
+        assert!(report.target_hit(), "score {} is out of the window", report.score());
+    }
+
+    #[test]
+    fn stops_at_the_max_generation(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let last = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let generations = last.clone();
+        let context = GenerationContext::new(&dictionary, 4, 4)
+            .with_target(TargetScore::at_least(1_000_000))
+            .with_progress(std::sync::Arc::new(move |progress: &Progress| generations.store(progress.generation(), std::sync::atomic::Ordering::Relaxed)))
+            .with_seed(5);
+
+        SimpleGeneticStrategy::new().with_max_generations(3).generate(&context).unwrap();
+        assert_eq!(last.load(std::sync::atomic::Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_get_board_is_reproducible(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let alphabet = default_alphabet();
+        let board = |seed| {
+            GeneticBoard::get_board_with(&dictionary, &alphabet, &ClassicScoring, 1_000_000, 4, 4, &mut StdRng::seed_from_u64(seed)).unwrap()
+        };
+        assert_eq!(board(9).hash(), board(9).hash());
+        assert_eq!(board(9).value().len(), 16);
+    }
+
+    #[test]
+    fn rejects_a_population_too_small_to_mate(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let context = GenerationContext::new(&dictionary, 4, 4);
+
+        let result = SimpleGeneticStrategy::new().with_population_size(1).generate(&context);
+        assert!(matches!(result, Err(BoggleError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+/// A board cell's tile, holding one or more letters such as `A` or `Qu`, or no letters at all for a blocked cell.
+///
+/// Letters are stored upper case, the tile is displayed with only its first letter capitalized
+/// and a blocked tile is displayed as `#`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tile {
+    letters: [char; Tile::MAX_LEN],
+    len: u8,
+}
+
+impl Tile {
+    /// the maximum number of letters a tile can hold
+    pub const MAX_LEN: usize = 3;
+
+    /// a blocked (blank) tile, no word can pass through it
+    pub const BLOCKED: Tile = Tile { letters: ['\0'; Tile::MAX_LEN], len: 0 };
+
+    /// initiate a new tile by given letters, e.g. `"Qu"`.
+    ///
+    /// returns `None` if the text is empty, longer than [`Tile::MAX_LEN`] or holds a non alphabetic character.
+    pub fn new(text: &str) -> Option<Self> {
+        let mut letters = ['\0'; Self::MAX_LEN];
+        let mut len = 0;
+        for ch in text.chars() {
+            if len == Self::MAX_LEN || !ch.is_alphabetic() {
+                return None;
+            }
+            letters[len] = ch.to_ascii_uppercase();
+            len += 1;
+        }
+
+        if len == 0 {
+            return None;
+        }
+
+        Some(Self { letters, len: len as u8 })
+    }
+
+    /// gets the tile's letters
+    pub fn letters(&self) -> &[char] {
+        &self.letters[..self.len as usize]
+    }
+
+    /// whether this tile is a blocked cell
+    pub fn is_blocked(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl From<char> for Tile {
+    fn from(ch: char) -> Self {
+        let mut letters = ['\0'; Self::MAX_LEN];
+        letters[0] = ch.to_ascii_uppercase();
+        Self { letters, len: 1 }
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_blocked() {
+            return write!(f, "#");
+        }
+
+        for (i, ch) in self.letters().iter().enumerate() {
+            if i == 0 {
+                write!(f, "{ch}")?;
+            } else {
+                write!(f, "{}", ch.to_ascii_lowercase())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// gets the `A` to `Z` single letter tiles
+pub fn default_alphabet() -> Vec<Tile> {
+    (b'A'..=b'Z').map(|ch| Tile::from(ch as char)).collect()
+}
@@ -1,41 +1,422 @@
-use std::collections::HashSet;
-use crate::boggle_dfs::{WordVisitor,BoggleDfsContext,BoggleDfs,get_word_score};
-use word_trie::trie::Trie;
+use std::fmt;
+use std::sync::Arc;
+use std::hash::Hash;
+use crate::boggle_dfs::{WordNodeVisitor,BoggleDfsContext,BoggleDfs};
+use crate::boggle_dictionary::{BoggleDictionary,NodeMap,NodeSet};
+use crate::compiled_dictionary::CompiledDictionary;
+use crate::tile::Tile;
+use crate::scoring::{ScoringRules,ClassicScoring};
+use crate::error::BoggleError;
 
-struct TotalScoreWordVisitor(HashSet<String>,u32);
+//the distinct words are told apart by their dictionary node
+struct TotalScoreWordVisitor<'r, N>(NodeSet<N>,u32,&'r dyn ScoringRules);
 
-impl TotalScoreWordVisitor {
+impl<N> TotalScoreWordVisitor<'_, N> {
     pub fn score(&self) -> u32 {
         self.1
     }
 }
 
-impl WordVisitor for TotalScoreWordVisitor {
-    fn visit(&mut self, word: &str, _: &Vec<u16>){
-        if !self.0.contains(word) {
-            self.1 += get_word_score(word);
-            self.0.insert(word.to_string());
+impl<N: Eq + Hash> WordNodeVisitor<N> for TotalScoreWordVisitor<'_, N> {
+    fn visit_node(&mut self, node: N, word: &str, _path: &[u16]){
+        if self.0.insert(node) {
+            self.1 += self.2.word_score(word);
         }
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct TotalBoggleScoreCalculator<'a> (BoggleDfsContext<'a>);
+#[derive(Debug)]
+pub struct TotalBoggleScoreCalculator<'a, D: BoggleDictionary = CompiledDictionary> (BoggleDfsContext<'a, D>, Arc<dyn ScoringRules>);
 
-impl <'a> TotalBoggleScoreCalculator<'a> {
-    pub fn new(dictionary : &'a Trie, width:usize, length:usize) -> Self{
-        Self(BoggleDfsContext::new(dictionary, width, length))
+impl <'a, D: BoggleDictionary> TotalBoggleScoreCalculator<'a, D> {
+    /// initiate a new calculator using the classic scoring rules
+    pub fn new(dictionary : &'a D, width:usize, length:usize) -> Self{
+        Self(BoggleDfsContext::new(dictionary, width, length), Arc::new(ClassicScoring))
     }
 
-    pub fn score(&mut self, board: & Vec<char>) -> u32{
-        if board.len() != self.0.count() {
-            panic!("The board size must be fit to the length:{0} and width:{1}", self.0.length(), self.0.width());
-        }
-       
-        let mut visitor = TotalScoreWordVisitor(HashSet::new(),0);
-        let mut session = BoggleDfs::new(&self.0 , board);
-        session.with_visitor(&mut visitor).search();
+    /// sets the scoring rules used to score the words
+    pub fn with_scoring(mut self, scoring: Arc<dyn ScoringRules>) -> Self{
+        self.1 = scoring;
+        self
+    }
 
-        visitor.score()
+    /// calculates the board's total score, fails if the board does not fit the calculator's width and length
+    pub fn score(&mut self, board: &[Tile]) -> Result<u32, BoggleError>{
+        BoggleError::check_board(self.0.width(), self.0.length(), board.len())?;
+       
+        Ok(total_score(&self.0, board, self.1.as_ref()))
     }  
-}
\ No newline at end of file
+
+    /// solves the board keeping every path of its words, so the score can be updated after a change of a few cells
+    /// without searching the whole board again. fails if the board does not fit the calculator's width and length
+    pub fn incremental(&self, board: &[Tile]) -> Result<IncrementalScoreCalculator<'a, D>, BoggleError>{
+        BoggleError::check_board(self.0.width(), self.0.length(), board.len())?;
+
+        let mut calculator = IncrementalScoreCalculator {
+            context: self.0.clone(),
+            scoring: self.1.clone(),
+            board: board.to_vec(),
+            paths: Vec::new(),
+            free_slots: Vec::new(),
+            words: NodeMap::default(),
+            score: 0,
+            last_change: Change::default(),
+        };
+        calculator.solve();
+
+        Ok(calculator)
+    }
+}
+
+impl<D: BoggleDictionary> Clone for TotalBoggleScoreCalculator<'_, D> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), self.1.clone())
+    }
+}
+
+/// calculates the total score of a board which fits the dfs context, by the given scoring rules
+pub(crate) fn total_score<D: BoggleDictionary>(context: &BoggleDfsContext<D>, board: &[Tile], scoring: &dyn ScoringRules) -> u32 {
+    let mut visitor = TotalScoreWordVisitor(NodeSet::default(),0,scoring);
+    let mut session = BoggleDfs::new(context, board);
+    session.search_nodes(&mut visitor);
+
+    visitor.score()
+}
+
+/// the parent of a path starting on its cell
+const NO_PARENT: u32 = u32::MAX;
+
+/// A traced path of cells spelling a prefix of a dictionary word, kept as its last cell and the path before it.
+#[derive(Clone, Copy)]
+struct TracedPath<N> {
+    node: N,
+    cells: u64,
+    cell: u16,
+    parent: u32,
+}
+
+/// A word of the board, with the number of its paths.
+#[derive(Clone, Copy)]
+struct TracedWord {
+    paths: usize,
+    score: u32,
+}
+
+/// A step of a change of an incremental calculator's board.
+#[derive(Clone, Copy)]
+enum PathStep<N> {
+    /// the path of the slot was forgotten
+    Forgotten(u32, TracedPath<N>),
+    /// a path was traced in the slot
+    Traced(u32),
+}
+
+/// The last change of an incremental calculator's board, with what it takes to undo it.
+#[derive(Clone)]
+struct Change<N> {
+    tiles: Vec<(usize, Tile)>,
+    steps: Vec<PathStep<N>>,
+    free_slots: Vec<u32>,
+    slots: usize,
+    score: u32,
+}
+
+impl<N> Default for Change<N> {
+    fn default() -> Self {
+        Self {
+            tiles: Vec::new(),
+            steps: Vec::new(),
+            free_slots: Vec::new(),
+            slots: 0,
+            score: 0,
+        }
+    }
+}
+
+/// The incremental score calculator struct, holding a solved board and every path of cells spelling a prefix of a word on it.
+///
+/// a change of a cell only forgets the paths through the cell and traces the new ones through it,
+/// the paths which do not touch the cell are kept as they are. a board of more than 64 cells is searched again on every change.
+pub struct IncrementalScoreCalculator<'a, D: BoggleDictionary = CompiledDictionary> {
+    context: BoggleDfsContext<'a, D>,
+    scoring: Arc<dyn ScoringRules>,
+    board: Vec<Tile>,
+    paths: Vec<Option<TracedPath<D::Node<'a>>>>,
+    free_slots: Vec<u32>,
+    //the words are keyed by their dictionary node
+    words: NodeMap<D::Node<'a>, TracedWord>,
+    score: u32,
+    last_change: Change<D::Node<'a>>,
+}
+
+impl<'a, D: BoggleDictionary> IncrementalScoreCalculator<'a, D> {
+    /// gets the board's total score
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// gets the board
+    pub fn board(&self) -> &[Tile] {
+        &self.board
+    }
+
+    /// puts the tile on the cell of the index and gets the board's new total score
+    ///
+    /// # Panics
+    ///
+    /// panics if the index is outside the board
+    pub fn set(&mut self, index: usize, tile: Tile) -> u32 {
+        self.begin_change();
+        self.change(index, tile);
+        self.score
+    }
+
+    /// swaps the tiles of the cells of the indexes and gets the board's new total score
+    ///
+    /// # Panics
+    ///
+    /// panics if an index is outside the board
+    pub fn swap(&mut self, a: usize, b: usize) -> u32 {
+        let (tile_a, tile_b) = (self.board[a], self.board[b]);
+        self.begin_change();
+        self.change(a, tile_b);
+        self.change(b, tile_a);
+        self.score
+    }
+
+    /// reverts the last `set` or `swap`, without searching the board again, and gets the board's total score.
+    /// a second undo does not change the board
+    pub fn undo(&mut self) -> u32 {
+        let change = std::mem::take(&mut self.last_change);
+        if change.tiles.is_empty() {
+            return self.score;
+        }
+
+        //the steps are reverted from the last one, the scores of the words are already known
+        for step in change.steps.iter().rev() {
+            match *step {
+                PathStep::Traced(slot) => {
+                    let path = self.paths[slot as usize].take().expect("a traced path");
+                    if let Some(word) = self.words.get_mut(&path.node) {
+                        word.paths -= 1;
+                    }
+                }
+                PathStep::Forgotten(slot, path) => {
+                    self.paths[slot as usize] = Some(path);
+                    if let Some(word) = self.words.get_mut(&path.node) {
+                        word.paths += 1;
+                    }
+                }
+            }
+        }
+        for &(index, tile) in change.tiles.iter().rev() {
+            self.board[index] = tile;
+        }
+        self.paths.truncate(change.slots);
+        self.free_slots = change.free_slots;
+        self.score = change.score;
+
+        self.score
+    }
+
+    fn begin_change(&mut self) {
+        self.last_change.tiles.clear();
+        self.last_change.steps.clear();
+        self.last_change.free_slots.clone_from(&self.free_slots);
+        self.last_change.slots = self.paths.len();
+        self.last_change.score = self.score;
+    }
+
+    /// puts the tile on the cell, forgets the paths through it and traces the new ones
+    fn change(&mut self, index: usize, tile: Tile) {
+        if self.board[index] == tile {
+            return;
+        }
+        self.last_change.tiles.push((index, self.board[index]));
+        self.board[index] = tile;
+        //the cells of a path are kept in a bitmask, which a board of more than 64 cells does not fit
+        let Some(around) = self.context.neighbour_mask(index) else {
+            self.score = total_score(&self.context, &self.board, self.scoring.as_ref());
+            return;
+        };
+
+        //every path through the cell is gone, along with the words it spelled.
+        //a path through the cell is only the parent of paths through it, so the kept paths' parents are kept
+        let cell = 1u64 << index;
+        for slot in 0..self.paths.len() {
+            if let Some(path) = self.paths[slot] && path.cells & cell != 0 {
+                self.paths[slot] = None;
+                self.free_slots.push(slot as u32);
+                self.last_change.steps.push(PathStep::Forgotten(slot as u32, path));
+                if self.context.dictionary().is_word(path.node) {
+                    self.forget_word(path.node);
+                }
+            }
+        }
+
+        //a new path through the cell starts on it, or goes on from a kept path ending next to it
+        self.trace(NO_PARENT, self.context.dictionary().root(), 0, index);
+        for slot in 0..self.paths.len() {
+            if let Some(path) = self.paths[slot] && path.cells & cell == 0 && around & (1 << path.cell) != 0 {
+                self.trace(slot as u32, path.node, path.cells, index);
+            }
+        }
+    }
+
+    /// traces every path of the board
+    fn solve(&mut self) {
+        if self.context.neighbour_mask(0).is_none() {
+            self.score = total_score(&self.context, &self.board, self.scoring.as_ref());
+            return;
+        }
+
+        let root = self.context.dictionary().root();
+        for cell in 0..self.board.len() {
+            self.trace(NO_PARENT, root, 0, cell);
+        }
+        self.last_change = Change::default();
+    }
+
+    /// keeps every path going on from the parent path through the cell, and counts the words they spell
+    fn trace(&mut self, parent: u32, mut node: D::Node<'a>, visited: u64, cell: usize) {
+        let tile = self.board[cell];
+        if tile.is_blocked() || visited & 1 << cell != 0 {
+            return;
+        }
+        for ch in tile.letters() {
+            match self.context.dictionary().step(node, ch.to_ascii_lowercase()) {
+                Some(next_node) => node = next_node,
+                None => return,
+            }
+        }
+
+        let cells = visited | 1 << cell;
+        let path = TracedPath { node, cells, cell: cell as u16, parent };
+        let slot = match self.free_slots.pop() {
+            Some(slot) => {
+                self.paths[slot as usize] = Some(path);
+                slot
+            }
+            None => {
+                self.paths.push(Some(path));
+                (self.paths.len() - 1) as u32
+            }
+        };
+        self.last_change.steps.push(PathStep::Traced(slot));
+        if self.context.dictionary().is_word(node) {
+            self.count_word(slot);
+        }
+
+        if self.context.dictionary().has_children(node) {
+            let mut next = self.context.neighbour_mask(cell).expect("a board of up to 64 cells") & !cells;
+            while next != 0 {
+                let next_cell = next.trailing_zeros() as usize;
+                next &= next - 1;
+                self.trace(slot, node, cells, next_cell);
+            }
+        }
+    }
+
+    fn count_word(&mut self, slot: u32) {
+        let node = self.paths[slot as usize].expect("a traced path").node;
+        let word = match self.words.get_mut(&node) {
+            Some(word) => word,
+            None => {
+                let score = self.scoring.word_score(&self.spell(slot));
+                self.words.entry(node).or_insert(TracedWord { paths: 0, score })
+            }
+        };
+        word.paths += 1;
+        if word.paths == 1 {
+            self.score += word.score;
+        }
+    }
+
+    fn forget_word(&mut self, node: D::Node<'a>) {
+        //a word's score is kept once it has no path, as it may be traced again
+        if let Some(word) = self.words.get_mut(&node) {
+            word.paths -= 1;
+            if word.paths == 0 {
+                self.score -= word.score;
+            }
+        }
+    }
+
+    /// gets the letters of the path in the slot
+    fn spell(&self, mut slot: u32) -> String {
+        let mut tiles = Vec::new();
+        while slot != NO_PARENT {
+            let path = self.paths[slot as usize].expect("the parent of a kept path is kept");
+            tiles.push(self.board[path.cell as usize]);
+            slot = path.parent;
+        }
+
+        tiles.iter().rev().flat_map(|tile| tile.letters()).collect()
+    }
+}
+
+impl<D: BoggleDictionary> Clone for IncrementalScoreCalculator<'_, D> {
+    fn clone(&self) -> Self {
+        Self {
+            context: self.context.clone(),
+            scoring: self.scoring.clone(),
+            board: self.board.clone(),
+            paths: self.paths.clone(),
+            free_slots: self.free_slots.clone(),
+            words: self.words.clone(),
+            score: self.score,
+            last_change: self.last_change.clone(),
+        }
+    }
+}
+
+impl<D: BoggleDictionary> fmt::Debug for IncrementalScoreCalculator<'_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IncrementalScoreCalculator")
+            .field("board", &self.board)
+            .field("score", &self.score)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use crate::dictionary::load_dictionary;
+    use crate::tile::default_alphabet;
+
+    #[test]
+    fn incremental_score_matches_a_full_search(){
+        let dictionary = load_dictionary("words.txt").unwrap();
+        let mut alphabet = default_alphabet();
+        alphabet.extend([Tile::new("Qu").unwrap(), Tile::BLOCKED]);
+        let mut rng = StdRng::seed_from_u64(4);
+
+        for (width, length) in [(4, 4), (5, 3), (9, 8)] {
+            let mut full = TotalBoggleScoreCalculator::new(&dictionary, width, length);
+            let board: Vec<Tile> = (0..width * length).map(|_| alphabet[rng.random_range(0..alphabet.len())]).collect();
+            let mut incremental = full.incremental(&board).unwrap();
+            assert_eq!(incremental.score(), full.score(&board).unwrap());
+
+            for _ in 0..50 {
+                let index = rng.random_range(0..board.len());
+                let score = if rng.random_bool(0.3) {
+                    incremental.swap(index, rng.random_range(0..board.len()))
+                } else {
+                    incremental.set(index, alphabet[rng.random_range(0..alphabet.len())])
+                };
+                assert_eq!(score, full.score(incremental.board()).unwrap());
+
+                //an undone change gives back the board and the score before it
+                if rng.random_bool(0.5) {
+                    let (board, score) = (incremental.board().to_vec(), incremental.score());
+                    incremental.set(rng.random_range(0..board.len()), alphabet[rng.random_range(0..alphabet.len())]);
+                    assert_eq!(incremental.undo(), score);
+                    assert_eq!(incremental.board(), board.as_slice());
+                }
+            }
+        }
+    }
+}